@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+/// Environment used to resolve substitutions that are not found in the document, like
+/// `${HOME}`, and to find the overrides set with
+/// [`HoconLoader::env_overrides`](struct.HoconLoader.html#method.env_overrides)
+///
+/// By default, the environment of the process is used. An `HashMap<String, String>` can be
+/// used as a controlled environment.
+///
+/// # Usage
+///
+/// ```rust
+/// # use hocon::{HoconLoader, Error};
+/// # use std::collections::HashMap;
+/// # fn main() -> Result<(), Error> {
+/// let mut env = HashMap::new();
+/// env.insert(String::from("HOME"), String::from("/home/tenant"));
+///
+/// let doc = HoconLoader::new()
+///     .with_env(env)
+///     .load_str(r#"{ data = ${HOME}"/data" }"#)?
+///     .hocon()?;
+///
+/// assert_eq!(doc["data"].as_string(), Some(String::from("/home/tenant/data")));
+/// # Ok(())
+/// # }
+/// ```
+pub trait EnvSource: std::fmt::Debug + Send + Sync {
+    /// Value of a variable, if it is set
+    fn var(&self, name: &str) -> Option<String>;
+
+    /// All the variables that are set
+    fn vars(&self) -> Vec<(String, String)>;
+}
+
+/// The environment of the process, from [`std::env`](https://doc.rust-lang.org/std/env/index.html)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnv;
+
+impl EnvSource for SystemEnv {
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .collect()
+    }
+}
+
+impl EnvSource for HashMap<String, String> {
+    fn var(&self, name: &str) -> Option<String> {
+        self.get(name).cloned()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
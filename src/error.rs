@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::Origin;
+
 /// Errors that can be encountered while reading a HOCON document
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum Error {
@@ -17,8 +19,20 @@ pub enum Error {
         path: String,
     },
     /// Error while parsing a document. The document is not valid HOCON
-    #[error("Error wile parsing document")]
-    Parse,
+    #[error(
+        "Error while parsing document{} at line {line}, column {column}: {message}",
+        .path.as_ref().map(|path| format!(" '{}'", path)).unwrap_or_default()
+    )]
+    Parse {
+        /// Path of the document being parsed, `None` if it was loaded from a string
+        path: Option<String>,
+        /// Line where parsing failed, starting at 1
+        line: usize,
+        /// Column where parsing failed, starting at 1
+        column: usize,
+        /// Description of what was expected at this position
+        message: String,
+    },
     /// Error including a document
     #[error("Error including document at '{path:?}'")]
     Include {
@@ -46,12 +60,98 @@ pub enum Error {
     /// Error getting a value because of an invalid key type
     #[error("Error getting a value because of an invalid key type")]
     InvalidKey,
+    /// Error getting a value by path because there is no value, or a `null` value, at this path
+    #[error("No configuration setting found for key '{path}'")]
+    Missing {
+        /// Path that was searched, as an HOCON path expression
+        path: String,
+    },
+    /// Error getting a value by path because the value is not of the expected type
+    #[error("'{path}' has type {found} rather than {expected}")]
+    WrongType {
+        /// Path of the value, as an HOCON path expression
+        path: String,
+        /// Type that was expected
+        expected: String,
+        /// Type of the value found
+        found: String,
+    },
+    /// Error parsing an HOCON path expression
+    #[error("Invalid path expression '{path}': {message}")]
+    BadPath {
+        /// The path expression
+        path: String,
+        /// Why the path expression is invalid
+        message: String,
+    },
     /// Error deserializing
     #[error("Error deserializing: {message:?}")]
     Deserialization {
         /// Error message returned from deserialization
         message: String,
     },
+    /// Error serializing
+    #[error("Error serializing: {message:?}")]
+    Serialization {
+        /// Error message returned from serialization
+        message: String,
+    },
+    /// Errors found in the documents when collecting errors with
+    /// [`collect_errors`](struct.HoconLoader.html#method.collect_errors)
+    #[error("Errors found while validating document: {}",
+        .errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Validation {
+        /// all the errors found, in the order they were encountered
+        errors: Vec<Diagnostic>,
+    },
+}
+
+/// An error found in a document, with the path of the value it affects and where this value
+/// was defined
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    path: String,
+    error: Error,
+    origin: Option<Origin>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(path: String, error: Error, origin: Option<Origin>) -> Self {
+        Self {
+            path,
+            error,
+            origin,
+        }
+    }
+
+    /// Path of the value affected by this error, as an HOCON path expression like `a.b."c.d"`.
+    /// Empty for errors affecting a whole document, like parse errors
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The error
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Where the value affected by this error was defined, if known
+    pub fn origin(&self) -> Option<&Origin> {
+        self.origin.as_ref()
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        write!(f, "{}", self.error)?;
+        if let Some(origin) = &self.origin {
+            write!(f, " ({})", origin)?;
+        }
+        Ok(())
+    }
 }
 
 /// this is only needed because this crate heavily relies on Clone and io:Error doesnt implement Clone
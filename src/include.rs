@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Format};
+
+/// Target of an `include` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Include {
+    /// `include "name"`, resolved as a file relative to the including document
+    Name(String),
+    /// `include file("path")`
+    File(String),
+    /// `include url("url")`
+    Url(String),
+    /// `include classpath("resource")`
+    Classpath(String),
+}
+
+impl Include {
+    /// The name, path, URL or resource being included
+    pub fn target(&self) -> &str {
+        match self {
+            Include::Name(s) | Include::File(s) | Include::Url(s) | Include::Classpath(s) => s,
+        }
+    }
+}
+
+/// A document found by an [`IncludeResolver`](trait.IncludeResolver.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludedDocument {
+    pub(crate) content: String,
+    pub(crate) format: Format,
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) name: Option<String>,
+}
+
+impl IncludedDocument {
+    /// New document with its content in the given format
+    pub fn new<S: Into<String>>(content: S, format: Format) -> Self {
+        Self {
+            content: content.into(),
+            format,
+            path: None,
+            name: None,
+        }
+    }
+
+    /// Path of the document. Relative includes inside the document will be resolved from
+    /// this path, and it will be used in errors and origins
+    pub fn with_path<P: Into<PathBuf>>(self, path: P) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Name of the document, used in errors and origins if it doesn't have a path
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+}
+
+/// Resolve the documents requested by `include` directives
+///
+/// A resolver set with [`HoconLoader::include_resolver`](struct.HoconLoader.html#method.include_resolver)
+/// is called for every include, before the default resolution of files and URLs. It can
+/// return `Ok(None)` to let the default resolution happen.
+///
+/// An `HashMap<String, String>` can be used as a resolver of in-memory documents, indexed by
+/// the target of the include. Their format is guessed from their extension.
+///
+/// # Usage
+///
+/// ```rust
+/// # use hocon::{HoconLoader, Error, Format, Include, IncludeResolver, IncludedDocument};
+/// # use std::path::Path;
+/// #[derive(Debug)]
+/// struct Secrets;
+///
+/// impl IncludeResolver for Secrets {
+///     fn resolve(
+///         &self,
+///         include: &Include,
+///         _from: Option<&Path>,
+///     ) -> Result<Option<IncludedDocument>, Error> {
+///         match include {
+///             Include::Url(url) if url.starts_with("secrets://") => Ok(Some(
+///                 IncludedDocument::new(r#"{ "password": "hunter2" }"#, Format::Json)
+///                     .with_name(url.as_str()),
+///             )),
+///             _ => Ok(None),
+///         }
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let doc = HoconLoader::new()
+///     .include_resolver(Secrets)
+///     .load_str(r#"db { include url("secrets://db") }"#)?
+///     .hocon()?;
+///
+/// assert_eq!(doc["db"]["password"].as_string(), Some(String::from("hunter2")));
+/// # Ok(())
+/// # }
+/// ```
+pub trait IncludeResolver: std::fmt::Debug + Send + Sync {
+    /// Resolve the document for this include. `from` is the path of the including document,
+    /// if it has one
+    fn resolve(
+        &self,
+        include: &Include,
+        from: Option<&Path>,
+    ) -> Result<Option<IncludedDocument>, Error>;
+}
+
+impl IncludeResolver for HashMap<String, String> {
+    fn resolve(
+        &self,
+        include: &Include,
+        _from: Option<&Path>,
+    ) -> Result<Option<IncludedDocument>, Error> {
+        Ok(self.get(include.target()).map(|content| {
+            IncludedDocument::new(content.as_str(), Format::of_path(include.target()))
+                .with_name(include.target())
+        }))
+    }
+}
+
+/// Resources available to `include classpath("...")`: embedded documents, then directories
+/// searched in order
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Classpath {
+    resources: HashMap<String, String>,
+    pub(crate) dirs: Vec<PathBuf>,
+}
+
+impl Classpath {
+    pub(crate) fn add_resource(&mut self, name: String, content: String) {
+        self.resources.insert(name, content);
+    }
+
+    /// Find the embedded resources for this name, with their format. Without an extension,
+    /// the `.properties`, `.json` and `.conf` resources with this name are found, in the order
+    /// they should be merged
+    pub(crate) fn find(&self, name: &str) -> Vec<(String, Format, &str)> {
+        let names = if Path::new(name).extension().is_some() {
+            vec![String::from(name)]
+        } else {
+            ["properties", "json", "conf"]
+                .iter()
+                .map(|extension| format!("{}.{}", name, extension))
+                .collect()
+        };
+        names
+            .into_iter()
+            .filter_map(|name| {
+                self.resources
+                    .get(&name)
+                    .map(|content| (name.clone(), Format::of_path(&name), content.as_str()))
+            })
+            .collect()
+    }
+}
+
+/// The file name of this include is a glob pattern, with `*` or `?`
+pub(crate) fn is_glob(path: &str) -> bool {
+    Path::new(path)
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(|name| name.contains(['*', '?']))
+        .unwrap_or(false)
+}
+
+/// `.conf`, `.json` and `.properties` files of a directory with a name matching the glob
+/// pattern, sorted by name. Hidden files are only matched by patterns starting with a `.`
+pub(crate) fn matching_files(dir: &Path, pattern: &str) -> std::io::Result<Vec<PathBuf>> {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let mut files = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().map(|ft| ft.is_file()).unwrap_or(false))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| {
+            matches!(
+                Path::new(name)
+                    .extension()
+                    .and_then(std::ffi::OsStr::to_str),
+                Some("conf") | Some("json") | Some("properties")
+            )
+        })
+        .filter(|name| !name.starts_with('.') || pattern.first() == Some(&'.'))
+        .filter(|name| glob_match(&pattern, &name.chars().collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files.into_iter().map(|name| dir.join(name)).collect())
+}
+
+/// Match a name against a glob pattern, going back to the last `*` to let it match one more
+/// character when the rest of the pattern doesn't match
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut last_star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                last_star = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(c) if *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match last_star {
+                Some((star, matched)) => {
+                    last_star = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        glob_match(
+            &pattern.chars().collect::<Vec<_>>(),
+            &name.chars().collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn glob_patterns_with_many_stars() {
+        let name = format!("{}c", "a".repeat(100));
+        assert!(!matches("a*a*a*a*a*a*a*a*a*a*b", &name));
+        assert!(matches("a*a*a*a*a*a*a*a*a*a*c", &name));
+        assert!(matches("**a**c", &name));
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(matches("*.conf", "a.conf"));
+        assert!(matches("*.conf", ".conf"));
+        assert!(matches("a?c*", "abc.json"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.conf", "a.json"));
+        assert!(!matches("a?c", "ac"));
+        assert!(matches("a*b*c", "abbbc"));
+        assert!(!matches("a*b*c", "abcb"));
+        assert!(is_glob("conf.d/*.conf"));
+        assert!(!is_glob("conf.d/app.conf"));
+    }
+
+    #[test]
+    fn files_sorted_by_name() {
+        let files =
+            matching_files(Path::new("tests/data"), "issue-26-?.conf").expect("during test");
+        assert_eq!(
+            files,
+            (1..=4)
+                .map(|i| Path::new("tests/data").join(format!("issue-26-{}.conf", i)))
+                .collect::<Vec<_>>()
+        );
+    }
+}
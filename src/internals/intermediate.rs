@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::{Hocon, HoconLoaderConfig};
+use crate::{Hocon, HoconLoaderConfig, Origin, Origins};
 
 use super::value::HoconValue;
 
@@ -152,6 +152,32 @@ impl Node {
         }
     }
 
+    fn collect_origins(&self, path: &mut Vec<String>, origins: &mut Origins) {
+        if let Node::Node { children, .. } = self {
+            let is_array = matches!(
+                children.first().map(|first| &first.key),
+                Some(HoconValue::Integer(_)) | Some(HoconValue::Null(_))
+            );
+            for (idx, child) in children.iter().enumerate() {
+                path.push(if is_array {
+                    idx.to_string()
+                } else {
+                    child.key.clone().string_value()
+                });
+                let value = child.value.borrow();
+                match (&*value, child.origin.borrow().as_ref()) {
+                    (Node::Leaf(_), Some(origin)) => origins.insert(path.clone(), origin.clone()),
+                    (Node::Node { children, .. }, Some(origin)) if children.is_empty() => {
+                        origins.insert(path.clone(), origin.clone())
+                    }
+                    _ => (),
+                }
+                value.collect_origins(path, origins);
+                path.pop();
+            }
+        }
+    }
+
     pub(crate) fn find_key(
         &self,
         config: &HoconLoaderConfig,
@@ -203,6 +229,7 @@ impl Node {
 pub(crate) struct Child {
     pub(crate) key: HoconValue,
     pub(crate) value: RefCell<Node>,
+    pub(crate) origin: RefCell<Option<Origin>>,
 }
 
 impl Child {
@@ -218,6 +245,7 @@ impl Child {
         Self {
             key: self.key.clone(),
             value: RefCell::new(self.value.clone().into_inner().deep_clone()),
+            origin: self.origin.clone(),
         }
     }
 }
@@ -228,6 +256,12 @@ pub(crate) struct HoconIntermediate {
 }
 
 impl HoconIntermediate {
+    pub(crate) fn origins(&self) -> Origins {
+        let mut origins = Origins::default();
+        self.tree.collect_origins(&mut vec![], &mut origins);
+        origins
+    }
+
     pub(crate) fn finalize(self, config: &HoconLoaderConfig) -> Result<Hocon, crate::Error> {
         #[allow(clippy::redundant_clone)]
         // looks like https://github.com/rust-lang/rust-clippy/issues/5707
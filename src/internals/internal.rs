@@ -1,27 +1,13 @@
-use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::rc::Rc;
 
-use crate::HoconLoaderConfig;
+use crate::{FileRead, HoconLoaderConfig, Include, IncludedDocument, Origin};
 
 use super::intermediate::{Child, HoconIntermediate, Node};
 use super::value::HoconValue;
 
-pub(crate) enum Include<'a> {
-    File(Cow<'a, str>),
-    Url(Cow<'a, str>),
-}
-impl<'a> Include<'a> {
-    fn included(&self) -> &Cow<'a, str> {
-        match self {
-            Include::File(s) => s,
-            Include::Url(s) => s,
-        }
-    }
-}
-
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct HoconInternal {
     pub(crate) internal: Hash,
@@ -48,33 +34,103 @@ impl HoconInternal {
                             .map(|s| HoconValue::String(String::from(s)))
                             .collect(),
                         HoconValue::String(value),
+                        None,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Values of the environment variables with a name starting with `prefix`, at the path
+    /// given by the rest of their name
+    pub(crate) fn from_env_overrides(
+        prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        let mut vars = vars
+            .into_iter()
+            .filter(|(name, _)| name.len() > prefix.len() && name.starts_with(prefix))
+            .collect::<Vec<_>>();
+        vars.sort();
+        Self {
+            internal: vars
+                .into_iter()
+                .map(|(name, value)| {
+                    (
+                        Self::env_override_path(&name[prefix.len()..])
+                            .into_iter()
+                            .map(HoconValue::String)
+                            .collect(),
+                        HoconValue::String(value),
+                        Some(Origin::new(Some(format!("env:{}", name)), None, None)),
                     )
                 })
                 .collect(),
         }
     }
 
+    /// Path from an environment variable name: `_` separates keys, `__` is a `-` and `___`
+    /// is a `_`
+    fn env_override_path(name: &str) -> Vec<String> {
+        let mut path = vec![String::new()];
+        let mut chars = name.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '_' {
+                path.last_mut().expect("path is never empty").push(c);
+                continue;
+            }
+            let mut underscores = 1;
+            while chars.next_if_eq(&'_').is_some() {
+                underscores += 1;
+            }
+            let key = path.last_mut().expect("path is never empty");
+            for _ in 0..underscores / 3 {
+                key.push('_');
+            }
+            match underscores % 3 {
+                1 => path.push(String::new()),
+                2 => key.push('-'),
+                _ => (),
+            }
+        }
+        path
+    }
+
     pub(crate) fn from_value(v: HoconValue) -> Self {
         Self {
-            internal: vec![(vec![], v)],
+            internal: vec![(vec![], v, None)],
         }
     }
 
     pub(crate) fn from_object(h: Hash) -> Self {
         if h.is_empty() {
             Self {
-                internal: vec![(vec![], HoconValue::EmptyObject)],
+                internal: vec![(vec![], HoconValue::EmptyObject, None)],
             }
         } else {
             Self {
                 internal: h
                     .into_iter()
-                    .map(|(k, v)| Self::add_root_to_includes(k, v))
+                    .map(|(k, v, origin)| {
+                        let (k, v) = Self::add_root_to_includes(k, v);
+                        (k, v, origin)
+                    })
                     .collect(),
             }
         }
     }
 
+    /// Set the origin of values that don't have one yet
+    pub(crate) fn with_origin(self, origin: impl Fn() -> Origin) -> Self {
+        Self {
+            internal: self
+                .internal
+                .into_iter()
+                .map(|(k, v, o)| (k, v, o.or_else(|| Some(origin()))))
+                .collect(),
+        }
+    }
+
     fn add_root_to_includes(k: Vec<HoconValue>, v: HoconValue) -> (Vec<HoconValue>, HoconValue) {
         match v {
             HoconValue::Included {
@@ -123,7 +179,7 @@ impl HoconInternal {
         }
         if a.is_empty() {
             Self {
-                internal: vec![(vec![], HoconValue::EmptyArray)],
+                internal: vec![(vec![], HoconValue::EmptyArray, None)],
             }
         } else {
             Self {
@@ -138,88 +194,257 @@ impl HoconInternal {
                         .internal
                         .into_iter()
                     })
-                    .map(|(k, v)| Self::add_root_to_includes(k, v))
+                    .map(|(k, v, origin)| {
+                        let (k, v) = Self::add_root_to_includes(k, v);
+                        (k, v, origin)
+                    })
                     .collect(),
             }
         }
     }
 
+    /// Parse an included document. A missing document is an error if the include is required,
+    /// and is skipped otherwise
     pub(crate) fn from_include(
         included: Include,
+        required: bool,
         config: &HoconLoaderConfig,
     ) -> Result<Self, crate::Error> {
-        if config.include_depth > config.max_include_depth {
-            Ok(Self {
-                internal: vec![(
-                    vec![HoconValue::String(included.included().to_string())],
-                    bad_value_or_err!(config, crate::Error::TooManyIncludes),
-                )],
-            })
-        } else if config.file_meta.is_none() {
-            Ok(Self {
-                internal: vec![(
-                    vec![HoconValue::String(included.included().to_string())],
-                    bad_value_or_err!(config, crate::Error::IncludeNotAllowedFromStr),
-                )],
-            })
+        if config.skip_includes {
+            return Ok(Self::empty());
+        }
+        let included_parsed = if config.include_depth > config.max_include_depth {
+            Err(crate::Error::TooManyIncludes)
         } else {
-            let included_parsed = match included {
-                Include::File(ref path) => {
-                    let include_config = config
-                        .included_from()
-                        .with_file(std::path::Path::new(path.as_ref()).to_path_buf());
-                    include_config
-                        .read_file()
-                        .map_err(|_| crate::error::Error::Include {
-                            path: path.to_string(),
-                        })
-                        .and_then(|s| include_config.parse_str_to_internal(s))
+            let from = config
+                .file_meta
+                .as_ref()
+                .map(|file_meta| file_meta.full_path.as_path());
+            match config
+                .include_resolver
+                .as_ref()
+                .map(|resolver| resolver.resolve(&included, from))
+            {
+                Some(Ok(Some(document))) => {
+                    Self::from_included_document(document, config).map(Some)
                 }
-                #[cfg(feature = "url-support")]
-                Include::Url(ref url) => {
-                    config
-                        .load_url(url)
-                        .map_err(|_| crate::error::Error::Include {
-                            path: url.to_string(),
-                        })
+                Some(Err(error)) => Err(error),
+                Some(Ok(None)) | None => Self::resolve_include(&included, config),
+            }
+        };
+
+        match included_parsed {
+            Ok(Some(included)) => Ok(Self {
+                internal: included
+                    .internal
+                    .into_iter()
+                    .map(|(path, value, origin)| {
+                        let value = match value {
+                            // values to concatenate to an array must stay at the top level to
+                            // be merged
+                            HoconValue::ToConcatToArray {
+                                value,
+                                original_path,
+                                item_id,
+                            } => HoconValue::ToConcatToArray {
+                                value: Box::new(HoconValue::Included {
+                                    value,
+                                    original_path: path.clone(),
+                                    include_root: None,
+                                }),
+                                original_path,
+                                item_id,
+                            },
+                            value => HoconValue::Included {
+                                value: Box::new(value),
+                                original_path: path.clone(),
+                                include_root: None,
+                            },
+                        };
+                        (path, value, origin)
+                    })
+                    .collect(),
+            }),
+            Ok(None) if !required => Ok(Self::empty()),
+            Ok(None) => Self::include_failed(
+                crate::Error::Include {
+                    path: included.target().to_string(),
+                },
+                config,
+            ),
+            Err(error) => Self::include_failed(error, config),
+        }
+    }
+
+    /// A failed include is an error in strict mode, and otherwise an error of the whole
+    /// document that doesn't add any value
+    fn include_failed(
+        error: crate::Error,
+        config: &HoconLoaderConfig,
+    ) -> Result<Self, crate::Error> {
+        if config.strict {
+            return Err(error);
+        }
+        config.log_include_error(error);
+        Ok(Self::empty())
+    }
+
+    /// Parse a document returned by an include resolver
+    fn from_included_document(
+        document: IncludedDocument,
+        config: &HoconLoaderConfig,
+    ) -> Result<Self, crate::Error> {
+        let include_config = match document.path {
+            Some(path) => config.included_from().with_file(path),
+            None => HoconLoaderConfig {
+                url: document.name,
+                ..config.included_from()
+            },
+        };
+        include_config
+            .parse_str_to_internal(FileRead::from_format(document.format, document.content))
+    }
+
+    /// Default resolution of includes, from files and URLs. Returns `None` if the included
+    /// document doesn't exist
+    fn resolve_include(
+        included: &Include,
+        config: &HoconLoaderConfig,
+    ) -> Result<Option<Self>, crate::Error> {
+        match included {
+            Include::Classpath(resource) => Self::resolve_classpath(resource, config),
+            Include::Name(path) => match &config.classpath_resource {
+                Some(resource) => {
+                    Self::resolve_classpath(&Self::classpath_sibling(resource, path), config)
                 }
-                #[cfg(not(feature = "url-support"))]
-                _ => Err(crate::error::Error::DisabledExternalUrl),
+                None => Self::resolve_file(path, config),
+            },
+            Include::File(path) => Self::resolve_file(path, config),
+            _ if config.file_meta.is_none() => Err(crate::Error::IncludeNotAllowedFromStr),
+            #[cfg(feature = "url-support")]
+            Include::Url(url) => config.load_url(url),
+            #[cfg(not(feature = "url-support"))]
+            Include::Url(_) => Err(crate::error::Error::DisabledExternalUrl),
+        }
+    }
+
+    /// Find a file relative to the including document, then in the include paths. Returns
+    /// `None` if the file doesn't exist
+    fn resolve_file(path: &str, config: &HoconLoaderConfig) -> Result<Option<Self>, crate::Error> {
+        if config.file_meta.is_none() && config.include_paths.is_empty() {
+            return Err(crate::Error::IncludeNotAllowedFromStr);
+        }
+        let glob = crate::include::is_glob(path);
+        let path = std::path::Path::new(path);
+        let relative_to_document = config.file_meta.as_ref().map(|_| path.to_path_buf());
+        let in_include_paths = config
+            .include_paths
+            .iter()
+            .filter(|_| path.is_relative())
+            .map(|dir| dir.join(path));
+        for candidate in relative_to_document.into_iter().chain(in_include_paths) {
+            let include_config = config.included_from().with_file(candidate);
+            let found = if glob {
+                Self::read_glob(&include_config)?
+            } else {
+                include_config
+                    .read_existing_file()?
+                    .map(|s| include_config.parse_str_to_internal(s))
+                    .transpose()?
             };
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        Ok(None)
+    }
 
-            match included_parsed {
-                Ok(included) => Ok(Self {
-                    internal: included
-                        .internal
-                        .into_iter()
-                        .map(|(path, value)| {
-                            (
-                                path.clone(),
-                                HoconValue::Included {
-                                    value: Box::new(value),
-                                    original_path: path,
-                                    include_root: None,
-                                },
-                            )
-                        })
-                        .collect(),
-                }),
-                Err(error) => Ok(Self {
-                    internal: vec![(
-                        vec![HoconValue::String(included.included().to_string())],
-                        bad_value_or_err!(config, error),
-                    )],
-                }),
+    /// Read all the files matching the glob pattern in the file name of this configuration,
+    /// in the order of their names. Returns `None` if no file matches
+    fn read_glob(config: &HoconLoaderConfig) -> Result<Option<Self>, crate::Error> {
+        let full_path = &config
+            .file_meta
+            .as_ref()
+            .expect("missing file metadata")
+            .full_path;
+        let files = match (
+            full_path.parent(),
+            full_path.file_name().and_then(std::ffi::OsStr::to_str),
+        ) {
+            (Some(dir), Some(pattern)) => {
+                let files = crate::include::matching_files(dir, pattern);
+                config.log_read(crate::Dependency::File {
+                    path: dir.to_path_buf(),
+                    found: files.is_ok(),
+                });
+                files.unwrap_or_default()
             }
+            _ => vec![],
+        };
+        if files.is_empty() {
+            return Ok(None);
+        }
+        let mut internal = Self::empty();
+        for file in files {
+            let file_config = config.with_file(file);
+            let s = file_config.read_file()?;
+            internal = internal.add(file_config.parse_str_to_internal(s)?);
+        }
+        Ok(Some(internal))
+    }
+
+    /// Find a resource in the classpath: first in the embedded resources, then in the
+    /// directories. Returns `None` if the resource doesn't exist
+    pub(crate) fn resolve_classpath(
+        resource: &str,
+        config: &HoconLoaderConfig,
+    ) -> Result<Option<Self>, crate::Error> {
+        let name = resource.trim_start_matches('/');
+        let embedded = config.classpath.find(name);
+        if !embedded.is_empty() {
+            let mut internal = Self::empty();
+            for (name, format, content) in embedded {
+                internal = internal.add(
+                    HoconLoaderConfig {
+                        url: Some(format!("classpath:{}", name)),
+                        classpath_resource: Some(name.clone()),
+                        ..config.included_from().without_file()
+                    }
+                    .parse_str_to_internal(FileRead::from_format(format, String::from(content)))?,
+                );
+            }
+            return Ok(Some(internal));
+        }
+        for dir in &config.classpath.dirs {
+            let include_config = HoconLoaderConfig {
+                classpath_resource: Some(String::from(name)),
+                ..config.included_from().with_file(dir.join(name))
+            };
+            if let Some(s) = include_config.read_existing_file()? {
+                return include_config.parse_str_to_internal(s).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Name of a resource included by a classpath resource, relative to the including resource
+    /// unless it starts with `/`
+    fn classpath_sibling(resource: &str, included: &str) -> String {
+        match resource.rfind('/') {
+            Some(end) if !included.starts_with('/') => {
+                format!("{}/{}", &resource[..end], included)
+            }
+            _ => String::from(included),
         }
     }
 
     pub(crate) fn add_include(
         &mut self,
         included: Include,
+        required: bool,
         config: &HoconLoaderConfig,
     ) -> Result<Self, crate::Error> {
-        let mut included = Self::from_include(included, config)?;
+        let mut included = Self::from_include(included, required, config)?;
 
         included.internal.append(&mut self.internal);
 
@@ -242,7 +467,10 @@ impl HoconInternal {
             internal: self
                 .internal
                 .into_iter()
-                .map(|(k, v)| (transform(k, v)))
+                .map(|(k, v, origin)| {
+                    let (k, v) = transform(k, v);
+                    (k, v, origin)
+                })
                 .collect(),
         }
     }
@@ -257,12 +485,13 @@ impl HoconInternal {
                 children: vec![],
                 key_hint: None,
             }),
+            origin: RefCell::new(None),
         });
 
         let mut concatenated_arrays: HashMap<Path, HashMap<HoconValue, i64>> = HashMap::new();
 
         let mut last_path_encoutered = vec![];
-        for (raw_path, item) in self.internal {
+        for (raw_path, item, origin) in self.internal {
             if raw_path.is_empty() {
                 continue;
             }
@@ -364,6 +593,7 @@ impl HoconInternal {
                         let new_child = Rc::new(Child {
                             key: path_item,
                             value: RefCell::new(Node::Leaf(HoconValue::Temp)),
+                            origin: RefCell::new(None),
                         });
 
                         old_node_value_for_optional_substitution = Some(old_value.clone());
@@ -384,6 +614,7 @@ impl HoconInternal {
                                 let new_child = Rc::new(Child {
                                     key: path_item.clone(),
                                     value: RefCell::new(Node::Leaf(HoconValue::Temp)),
+                                    origin: RefCell::new(None),
                                 });
                                 new_children.push(Rc::clone(&new_child));
 
@@ -402,6 +633,7 @@ impl HoconInternal {
                                     value: RefCell::new(Node::Leaf(HoconValue::Null(
                                         String::from("0"),
                                     ))),
+                                    origin: RefCell::new(None),
                                 });
                                 let mut new_children = if children.is_empty() {
                                     children.clone()
@@ -444,6 +676,7 @@ impl HoconInternal {
 
                 current_node = target_child;
             }
+            current_node.origin.replace(origin);
             let mut leaf = current_node.value.borrow_mut();
 
             *leaf = match leaf_value? {
@@ -472,41 +705,72 @@ impl HoconInternal {
 }
 
 pub(crate) type Path = Vec<HoconValue>;
-pub(crate) type Hash = Vec<(Path, HoconValue)>;
+pub(crate) type Hash = Vec<(Path, HoconValue, Option<Origin>)>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn include_errors(config: &HoconLoaderConfig) -> Vec<crate::Error> {
+        config
+            .include_errors
+            .lock()
+            .expect("during test")
+            .iter()
+            .map(|diagnostic| diagnostic.error().clone())
+            .collect()
+    }
+
     #[test]
     fn max_depth_of_include() {
+        let config = HoconLoaderConfig {
+            include_depth: 15,
+            file_meta: Some(crate::ConfFileMeta::from_path(
+                std::path::Path::new("file.conf").to_path_buf(),
+            )),
+            ..Default::default()
+        };
         let val = dbg!(HoconInternal::from_include(
-            Include::File(Cow::from("file.conf")),
-            &HoconLoaderConfig {
-                include_depth: 15,
-                file_meta: Some(crate::ConfFileMeta::from_path(
-                    std::path::Path::new("file.conf").to_path_buf()
-                )),
-                ..Default::default()
-            }
+            Include::File(String::from("file.conf")),
+            false,
+            &config
         ))
         .expect("during test");
 
+        assert_eq!(val, HoconInternal::empty());
+        assert_eq!(include_errors(&config), vec![crate::Error::TooManyIncludes]);
+    }
+
+    #[test]
+    fn missing_file_included() {
+        let config = HoconLoaderConfig {
+            include_depth: 5,
+            file_meta: Some(crate::ConfFileMeta::from_path(
+                std::path::Path::new("file.conf").to_path_buf(),
+            )),
+            ..Default::default()
+        };
+        let val = dbg!(HoconInternal::from_include(
+            Include::File(String::from("file.conf")),
+            true,
+            &config
+        ))
+        .expect("during test");
+
+        assert_eq!(val, HoconInternal::empty());
         assert_eq!(
-            val,
-            HoconInternal {
-                internal: vec![(
-                    vec![HoconValue::String(String::from("file.conf"))],
-                    HoconValue::BadValue(crate::Error::TooManyIncludes)
-                )]
-            }
+            include_errors(&config),
+            vec![crate::Error::Include {
+                path: String::from("file.conf")
+            }]
         );
     }
 
     #[test]
-    fn missing_file_included() {
+    fn missing_optional_file_included() {
         let val = dbg!(HoconInternal::from_include(
-            Include::File(Cow::from("file.conf")),
+            Include::File(String::from("file.conf")),
+            false,
             &HoconLoaderConfig {
                 include_depth: 5,
                 file_meta: Some(crate::ConfFileMeta::from_path(
@@ -517,14 +781,39 @@ mod tests {
         ))
         .expect("during test");
 
+        assert_eq!(val, HoconInternal::empty());
+    }
+
+    #[test]
+    fn env_overrides_paths() {
+        assert_eq!(
+            HoconInternal::env_override_path("a_b__c___d"),
+            vec!["a", "b-c_d"]
+        );
+        assert_eq!(HoconInternal::env_override_path("A____B"), vec!["A_", "B"]);
+
+        let val = HoconInternal::from_env_overrides(
+            "CONFIG_FORCE_",
+            vec![
+                (String::from("CONFIG_FORCE_x_y"), String::from("1")),
+                (String::from("CONFIG_FORCE_"), String::from("2")),
+                (String::from("OTHER_x"), String::from("3")),
+            ],
+        );
         assert_eq!(
             val,
             HoconInternal {
                 internal: vec![(
-                    vec![HoconValue::String(String::from("file.conf"))],
-                    HoconValue::BadValue(crate::Error::Include {
-                        path: String::from("file.conf")
-                    })
+                    vec![
+                        HoconValue::String(String::from("x")),
+                        HoconValue::String(String::from("y"))
+                    ],
+                    HoconValue::String(String::from("1")),
+                    Some(Origin::new(
+                        Some(String::from("env:CONFIG_FORCE_x_y")),
+                        None,
+                        None
+                    ))
                 )]
             }
         );
@@ -158,8 +158,8 @@ impl HoconValue {
                     ) {
                         (_, true, Err(err)) | (_, true, Ok(Hocon::BadValue(err))) => {
                             match (
-                                std::env::var(
-                                    v.to_path()
+                                config.env.var(
+                                    &v.to_path()
                                         .into_iter()
                                         .map(HoconValue::string_value)
                                         .collect::<Vec<_>>()
@@ -168,7 +168,7 @@ impl HoconValue {
                                 optional,
                                 original,
                             ) {
-                                (Ok(val), _, _) => Ok(Hocon::String(val)),
+                                (Some(val), _, _) => Ok(Hocon::String(val)),
                                 (_, true, Some(val)) => val.simple_finalize(),
                                 _ => Ok(public_bad_value_or_err!(config, err)),
                             }
@@ -265,6 +265,7 @@ impl HoconValue {
                             Node::Leaf(_) => vec![std::rc::Rc::new(Child {
                                 key: HoconValue::Integer(0),
                                 value: std::cell::RefCell::new(node),
+                                origin: std::cell::RefCell::new(None),
                             })],
                             Node::Node { children, .. } => children,
                         })
@@ -278,6 +279,7 @@ impl HoconValue {
                             _ => Some(std::rc::Rc::new(Child {
                                 key: HoconValue::Integer(i as i64),
                                 value: child.value.clone(),
+                                origin: child.origin.clone(),
                             })),
                         })
                         .collect::<Vec<_>>();
@@ -156,7 +156,10 @@
 //!
 //! ### `serde-support`
 //!
-//! This feature enable deserializing to a `struct` implementing `Deserialize` using `serde`
+//! This feature enable deserializing to a `struct` implementing `Deserialize` using `serde`,
+//! and building an `Hocon` value from a type implementing `Serialize` with
+//! [`ser::to_hocon`](ser/fn.to_hocon.html). `Hocon` itself implements `Serialize` and
+//! `Deserialize`, so it can be used as a field of a struct or converted to any serde format
 //!
 //! ```rust
 //! use serde::Deserialize;
@@ -185,17 +188,31 @@ use std::path::Path;
 mod internals;
 mod parser;
 mod value;
-pub use value::Hocon;
+pub use value::{Entry, Hocon};
 mod error;
-pub use error::Error;
+pub use error::{Diagnostic, Error};
 pub(crate) mod helper;
 mod loader_config;
+pub use loader_config::Format;
 pub(crate) use loader_config::*;
+mod env;
+pub use env::{EnvSource, SystemEnv};
+mod include;
+pub use include::{Include, IncludeResolver, IncludedDocument};
+mod origin;
+pub use origin::{Dependency, Origin, Origins};
+mod path;
+mod render;
+pub use render::RenderOptions;
+mod watcher;
+pub use watcher::ConfigWatcher;
 
 #[cfg(feature = "serde-support")]
 mod serde;
 #[cfg(feature = "serde-support")]
 pub use crate::serde::de;
+#[cfg(feature = "serde-support")]
+pub use crate::serde::ser;
 
 /// Helper to load an HOCON file. This is used to set up the HOCON loader's option,
 /// like strict mode, disabling system environment, and to buffer several documents.
@@ -233,6 +250,8 @@ pub use crate::serde::de;
 pub struct HoconLoader {
     config: HoconLoaderConfig,
     internal: internals::HoconInternal,
+    errors: Vec<Diagnostic>,
+    dependencies: Vec<Dependency>,
 }
 
 impl Default for HoconLoader {
@@ -247,6 +266,8 @@ impl HoconLoader {
         Self {
             config: HoconLoaderConfig::default(),
             internal: internals::HoconInternal::empty(),
+            errors: vec![],
+            dependencies: vec![],
         }
     }
 
@@ -299,6 +320,85 @@ impl HoconLoader {
         }
     }
 
+    /// Override values with the environment variables with a name starting with `prefix`,
+    /// like `CONFIG_FORCE_`. The rest of the name of a variable is the path of the value
+    /// it overrides: `_` separates keys, `__` is a `-` and `___` is a `_`. Values are strings.
+    ///
+    /// Overrides are applied after all the loaded documents, when building the `Hocon`
+    /// document
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # use std::collections::HashMap;
+    /// # fn main() -> Result<(), Error> {
+    /// let mut env = HashMap::new();
+    /// env.insert(String::from("CONFIG_FORCE_server_max__connections"), String::from("100"));
+    /// env.insert(String::from("CONFIG_FORCE_server_log___level"), String::from("debug"));
+    ///
+    /// let doc = HoconLoader::new()
+    ///     .with_env(env)
+    ///     .env_overrides("CONFIG_FORCE_")
+    ///     .load_str(r#"server { max-connections = 10, port = 8080 }"#)?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["server"]["max-connections"].as_i64(), Some(100));
+    /// assert_eq!(doc["server"]["log_level"].as_string(), Some(String::from("debug")));
+    /// assert_eq!(doc["server"]["port"].as_i64(), Some(8080));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn env_overrides(&self, prefix: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                env_overrides: Some(String::from(prefix)),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Load the overlays of this profile with every file: after loading `name.conf`, the
+    /// files `name.<profile>.properties`, `name.<profile>.json` and `name.<profile>.conf` are
+    /// loaded too, in this order, if they exist
+    ///
+    /// Several profiles can be set, their overlays are loaded in the order the profiles were
+    /// set
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .with_profile("prod")
+    ///     .load_file("tests/data/profiles/application.conf")?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["db"]["host"].as_string(), Some(String::from("db.prod.example.com")));
+    /// assert_eq!(doc["db"]["port"].as_i64(), Some(5432));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_profile(&self, profile: &str) -> Self {
+        let mut config = self.config.clone();
+        config.profiles.push(String::from(profile));
+        Self {
+            config,
+            ..self.clone()
+        }
+    }
+
+    /// Use this [`EnvSource`](trait.EnvSource.html) instead of the environment of the process
+    /// to resolve substitutions not found in the document, and to find the
+    /// [`env_overrides`](struct.HoconLoader.html#method.env_overrides)
+    pub fn with_env<E: EnvSource + 'static>(&self, env: E) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                env: std::sync::Arc::new(env),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
     /// Disable loading included files from external urls.
     ///
     /// # Example HOCON document
@@ -386,12 +486,169 @@ impl HoconLoader {
         Self {
             config: HoconLoaderConfig {
                 strict: true,
+                collect_errors: false,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Sets the HOCON loader to validate the whole documents, and return all the errors
+    /// encountered at once in an [`Error::Validation`](enum.Error.html#variant.Validation)
+    /// instead of stopping at the first one like in [`strict`](struct.HoconLoader.html#method.strict)
+    /// mode
+    ///
+    /// Documents that can't be read or parsed are skipped, and their errors are collected along
+    /// with missing required includes and unresolved substitutions
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let result = HoconLoader::new()
+    ///     .collect_errors()
+    ///     .load_str(r#"{ a = ${b}, c = [ ${d} ] }"#)?
+    ///     .load_str(r#"{ e = "f }"#)?
+    ///     .hocon();
+    ///
+    /// match result {
+    ///     Err(Error::Validation { errors }) => {
+    ///         let paths: Vec<_> = errors.iter().map(|error| error.path()).collect();
+    ///         assert_eq!(paths, vec!["", "a", "c.0"]);
+    ///     }
+    ///     _ => panic!("document should be invalid"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn collect_errors(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                strict: false,
+                collect_errors: true,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Keep the error to report it later if collecting errors, or return it
+    fn collect_error(mut self, error: Error) -> Result<Self, Error> {
+        if self.config.collect_errors {
+            let origin = match &error {
+                Error::Parse { path, line, .. } => {
+                    Some(Origin::new(path.clone(), Some(*line), None))
+                }
+                _ => None,
+            };
+            self.errors
+                .push(Diagnostic::new(String::new(), error, origin));
+            Ok(self)
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Errors collected while loading the documents, followed by the errors of all the
+    /// `BadValue`s of the document
+    fn diagnostics(errors: Vec<Diagnostic>, hocon: &Hocon, origins: &Origins) -> Vec<Diagnostic> {
+        let mut diagnostics = errors;
+        diagnostics.extend(hocon.bad_values().into_iter().map(|(path, error)| {
+            Diagnostic::new(
+                render::render_path(&path),
+                error.clone(),
+                origins.get(&path).cloned(),
+            )
+        }));
+        diagnostics
+    }
+
+    /// Use this [`IncludeResolver`](trait.IncludeResolver.html) to find the documents
+    /// requested by `include` directives. It is called before the default resolution of files
+    /// and URLs, and also when loading a string
+    pub fn include_resolver<R: IncludeResolver + 'static>(&self, resolver: R) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                include_resolver: Some(std::sync::Arc::new(resolver)),
                 ..self.config.clone()
             },
             ..self.clone()
         }
     }
 
+    /// Register an embedded resource, available to `include classpath("name")`. Its format
+    /// is guessed from the extension of its name. An include without extension, like
+    /// `include classpath("reference")`, reads the `.conf`, `.json` and `.properties`
+    /// resources with this name. An `include "name"` inside a classpath resource includes the
+    /// resource with this name relative to the including one, or from the root of the
+    /// classpath if it starts with `/`
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .classpath_resource("reference.conf", include_str!("../tests/data/basic.conf"))
+    ///     .load_str(r#"{ include classpath("reference.conf"), a = 7 }"#)?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["a"].as_i64(), Some(7));
+    /// assert_eq!(doc["b"].as_f64(), Some(6.7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn classpath_resource<N: Into<String>, C: Into<String>>(
+        &self,
+        name: N,
+        content: C,
+    ) -> Self {
+        let mut config = self.config.clone();
+        std::sync::Arc::make_mut(&mut config.classpath).add_resource(name.into(), content.into());
+        Self {
+            config,
+            ..self.clone()
+        }
+    }
+
+    /// Add a directory searched for `include classpath("...")` resources, after the embedded
+    /// resources and the directories added before
+    pub fn classpath_dir<P: AsRef<Path>>(&self, dir: P) -> Self {
+        let dir = Self::absolute_path(&dir).unwrap_or_else(|_| dir.as_ref().to_path_buf());
+        let mut config = self.config.clone();
+        std::sync::Arc::make_mut(&mut config.classpath)
+            .dirs
+            .push(dir);
+        Self {
+            config,
+            ..self.clone()
+        }
+    }
+
+    /// Add a directory searched for included files that are not found relative to the
+    /// including document, after the directories added before. This lets strings loaded
+    /// with [`load_str`](struct.HoconLoader.html#method.load_str) include files too
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .include_path("tests/data")
+    ///     .load_str(r#"{ include "basic.conf", a = 7 }"#)?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["a"].as_i64(), Some(7));
+    /// assert_eq!(doc["b"].as_f64(), Some(6.7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn include_path<P: AsRef<Path>>(&self, dir: P) -> Self {
+        let dir = Self::absolute_path(&dir).unwrap_or_else(|_| dir.as_ref().to_path_buf());
+        let mut config = self.config.clone();
+        config.include_paths.push(dir);
+        Self {
+            config,
+            ..self.clone()
+        }
+    }
+
     /// Set a new maximum include depth, by default 10
     pub fn max_include_depth(&self, new_max_depth: u8) -> Self {
         Self {
@@ -404,14 +661,18 @@ impl HoconLoader {
     }
 
     pub(crate) fn load_from_str_of_conf_file(self, s: FileRead) -> Result<Self, Error> {
-        Ok(Self {
-            internal: self.internal.add(self.config.parse_str_to_internal(s)?),
-            config: self.config,
-        })
+        match self.config.parse_str_to_internal(s) {
+            Ok(internal) => Ok(Self {
+                internal: self.internal.add(internal),
+                ..self
+            }),
+            Err(error) => self.collect_error(error),
+        }
     }
 
-    /// Load a string containing an `Hocon` document. Includes are not supported when
-    /// loading from a string
+    /// Load a string containing an `Hocon` document. Only `classpath()` includes, and files
+    /// found in the [`include_path`](struct.HoconLoader.html#method.include_path) directories,
+    /// are supported when loading from a string
     ///
     /// # Errors
     ///
@@ -420,31 +681,124 @@ impl HoconLoader {
     /// # Additional errors in strict mode
     ///
     /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
-    /// if there is an include in the string
+    ///   if there is an `url()` include in the string, or a file include without any
+    ///   [`include_path`](struct.HoconLoader.html#method.include_path)
     pub fn load_str(self, s: &str) -> Result<Self, Error> {
-        self.load_from_str_of_conf_file(FileRead {
-            hocon: Some(String::from(s)),
-            ..Default::default()
-        })
+        self.load_str_as(Format::Hocon, s)
     }
 
-    /// Load the HOCON configuration file containing an `Hocon` document
+    /// Load a string containing a JSON document
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    pub fn load_json_str(self, s: &str) -> Result<Self, Error> {
+        self.load_str_as(Format::Json, s)
+    }
+
+    /// Load a string containing a Java `.properties` document
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_properties_str("a.b=c\nd=7")?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["a"]["b"].as_string(), Some(String::from("c")));
+    /// assert_eq!(doc["d"].as_i64(), Some(7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    pub fn load_properties_str(self, s: &str) -> Result<Self, Error> {
+        self.load_str_as(Format::Properties, s)
+    }
+
+    /// Load a string containing a document in the given [`Format`](enum.Format.html).
+    /// Only `classpath()` includes, and files found in the
+    /// [`include_path`](struct.HoconLoader.html#method.include_path) directories, are
+    /// supported when loading from a string
     ///
     /// # Errors
     ///
-    /// * [`Error::File`](enum.Error.html#variant.File) if there was an error reading the
-    /// file content
     /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
     ///
     /// # Additional errors in strict mode
     ///
-    /// * [`Error::TooManyIncludes`](enum.Error.html#variant.TooManyIncludes)
-    /// if there are too many included files within included files. The limit can be
-    /// changed with [`max_include_depth`](struct.HoconLoader.html#method.max_include_depth)
-    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Self, Error> {
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    ///   if there is an `url()` include in the string, or a file include without any
+    ///   [`include_path`](struct.HoconLoader.html#method.include_path)
+    pub fn load_str_as(self, format: Format, s: &str) -> Result<Self, Error> {
+        self.load_reader(s.as_bytes(), format, None)
+    }
+
+    /// Load a document in the given [`Format`](enum.Format.html) from any reader, like
+    /// stdin or a decompressed stream
+    ///
+    /// The document can be given a virtual `path`, used to resolve relative includes and
+    /// to report errors and origins. Without a path, only `classpath()` includes and files
+    /// found in the [`include_path`](struct.HoconLoader.html#method.include_path) directories
+    /// are supported
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Format};
+    /// # use std::path::Path;
+    /// # fn main() -> Result<(), Error> {
+    /// let stream = std::io::Cursor::new(r#"include "basic.conf""#);
+    ///
+    /// let doc = HoconLoader::new()
+    ///     .load_reader(stream, Format::Hocon, Some(Path::new("tests/data/stream.conf")))?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["a"].as_i64(), Some(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Io`](enum.Error.html#variant.Io) if there was an error reading from the
+    ///   reader
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    ///   if there is an include and no path was given
+    pub fn load_reader<R: std::io::Read>(
+        self,
+        mut reader: R,
+        format: Format,
+        path: Option<&Path>,
+    ) -> Result<Self, Error> {
+        let mut s = String::new();
+        if let Err(error) = reader.read_to_string(&mut s) {
+            return self.collect_error(error.into());
+        }
+        self.with_read_log(|loader| loader.load_read_str(s, format, path))
+    }
+
+    fn load_read_str(self, s: String, format: Format, path: Option<&Path>) -> Result<Self, Error> {
+        let config = self.config.clone();
+        let read_config = match path.map(Self::absolute_path) {
+            Some(Ok(path)) => config.with_file(path),
+            Some(Err(error)) => return self.collect_error(error),
+            None => config.without_file(),
+        };
+        Self {
+            config: read_config,
+            ..self
+        }
+        .load_from_str_of_conf_file(FileRead::from_format(format, s))
+        .map(|loader| Self { config, ..loader })
+    }
+
+    fn absolute_path<P: AsRef<Path>>(path: P) -> Result<std::path::PathBuf, Error> {
         let mut file_path = path.as_ref().to_path_buf();
-        // pub fn load_file(&self, path: &str) -> Result<Self, Error> {
-        // let mut file_path = Path::new(path).to_path_buf();
         if !file_path.has_root() {
             let mut current_path = std::env::current_dir().map_err(|_| Error::File {
                 path: String::from(path.as_ref().to_str().unwrap_or("invalid path")),
@@ -452,8 +806,63 @@ impl HoconLoader {
             current_path.push(path.as_ref());
             file_path = current_path;
         }
+        Ok(file_path)
+    }
+
+    /// Load the HOCON configuration file containing an `Hocon` document
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::File`](enum.Error.html#variant.File) if there was an error reading the
+    /// file content
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::TooManyIncludes`](enum.Error.html#variant.TooManyIncludes)
+    /// if there are too many included files within included files. The limit can be
+    /// changed with [`max_include_depth`](struct.HoconLoader.html#method.max_include_depth)
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Self, Error> {
+        let file_path = Self::absolute_path(path)?;
+        let mut loader = self.load_single_file(file_path.clone())?;
+        for overlay in self.profile_overlays(&file_path) {
+            if overlay.is_file() {
+                loader = loader.load_single_file(overlay)?;
+            } else {
+                loader.dependencies.push(Dependency::File {
+                    path: overlay,
+                    found: false,
+                });
+            }
+        }
+        Ok(loader)
+    }
+
+    /// Profile overlays of a file, in the order they should be loaded
+    fn profile_overlays(&self, file_path: &Path) -> Vec<std::path::PathBuf> {
+        let stem = match file_path.file_stem().and_then(std::ffi::OsStr::to_str) {
+            Some(stem) => stem,
+            None => return vec![],
+        };
+        self.config
+            .profiles
+            .iter()
+            .flat_map(|profile| {
+                ["properties", "json", "conf"].iter().map(move |extension| {
+                    file_path.with_file_name(format!("{}.{}.{}", stem, profile, extension))
+                })
+            })
+            .collect()
+    }
+
+    /// Load a file, without its profile overlays
+    fn load_single_file(&self, file_path: std::path::PathBuf) -> Result<Self, Error> {
+        self.with_read_log(|loader| loader.load_single_file_logged(file_path))
+    }
+
+    fn load_single_file_logged(self, file_path: std::path::PathBuf) -> Result<Self, Error> {
         let conf = self.config.with_file(file_path);
-        let contents = conf.read_file().map_err(|err| {
+        let contents = match conf.read_file().map_err(|err| {
             let path = match err {
                 Error::File { path } => path,
                 Error::Include { path } => path,
@@ -461,27 +870,330 @@ impl HoconLoader {
                 _ => "unmatched error".to_string(),
             };
             Error::File { path }
-        })?;
+        }) {
+            Ok(contents) => contents,
+            Err(error) => return self.collect_error(error),
+        };
         Self {
             config: conf,
-            ..self.clone()
+            ..self
         }
         .load_from_str_of_conf_file(contents)
     }
 
+    /// Run a load with a new log of the files and URLs read, and keep them as dependencies.
+    /// The includes that failed during this load are kept as errors
+    fn with_read_log(&self, load: impl FnOnce(Self) -> Result<Self, Error>) -> Result<Self, Error> {
+        let read_log = std::sync::Arc::default();
+        let include_errors = std::sync::Arc::default();
+        let loader = Self {
+            config: HoconLoaderConfig {
+                read_log: std::sync::Arc::clone(&read_log),
+                include_errors: std::sync::Arc::clone(&include_errors),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        };
+        let mut loaded = load(loader)?;
+        if let Ok(mut read_log) = read_log.lock() {
+            loaded.dependencies.append(&mut read_log);
+        }
+        if let Ok(mut include_errors) = include_errors.lock() {
+            loaded.errors.append(&mut include_errors);
+        }
+        Ok(loaded)
+    }
+
+    /// Load the standard documents of an application, like `ConfigFactory.load()` of
+    /// Typesafe Config, in this order:
+    ///
+    /// 1. the `reference.conf`, `reference.json` and `reference.properties` classpath
+    ///    resources, registered with [`classpath_resource`](struct.HoconLoader.html#method.classpath_resource)
+    ///    or found in a [`classpath_dir`](struct.HoconLoader.html#method.classpath_dir)
+    /// 2. the `application` classpath resources, then the `application.conf`,
+    ///    `application.json` and `application.properties` files of the current directory
+    /// 3. the `config_file` given, usually from a command line flag, or else the file named by
+    ///    the `<APP_NAME>_CONFIG_FILE` environment variable, where `<APP_NAME>` is `app_name`
+    ///    in uppercase with `_` instead of other characters than letters and digits
+    /// 4. the environment variables starting with `CONFIG_FORCE_`, see
+    ///    [`env_overrides`](struct.HoconLoader.html#method.env_overrides), unless another
+    ///    prefix was already set
+    ///
+    /// Missing documents are skipped, except the file given in step 3. Substitutions that
+    /// refer to values not defined by the documents loaded before them are resolved once all
+    /// documents are merged, so `reference.conf` can refer to values that must be set by
+    /// `application.conf`
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # use std::collections::HashMap;
+    /// # fn main() -> Result<(), Error> {
+    /// let mut env = HashMap::new();
+    /// env.insert(String::from("MY_APP_CONFIG_FILE"), String::from("tests/data/basic.conf"));
+    /// env.insert(String::from("CONFIG_FORCE_server_port"), String::from("9090"));
+    ///
+    /// let doc = HoconLoader::new()
+    ///     .with_env(env)
+    ///     .classpath_resource(
+    ///         "reference.conf",
+    ///         r#"server { port = 8080, url = "http://"${server.host} }"#,
+    ///     )
+    ///     .classpath_resource("application.conf", "server.host = example.com")
+    ///     .load_default("my-app", None)?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc["server"]["url"].as_string(), Some(String::from("http://example.com")));
+    /// assert_eq!(doc["server"]["port"].as_i64(), Some(9090));
+    /// assert_eq!(doc["b"].as_f64(), Some(6.7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::File`](enum.Error.html#variant.File) if the file given in step 3 can't be
+    ///   read
+    /// * the errors of [`load_file`](struct.HoconLoader.html#method.load_file) for each
+    ///   document
+    pub fn load_default(&self, app_name: &str, config_file: Option<&Path>) -> Result<Self, Error> {
+        let mut loader = self
+            .clone()
+            .load_classpath("reference")?
+            .load_classpath("application")?
+            .load_file("application")?;
+        let config_file_var = format!(
+            "{}_CONFIG_FILE",
+            app_name
+                .chars()
+                .map(|c| match c {
+                    c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+                    _ => '_',
+                })
+                .collect::<String>()
+        );
+        if let Some(config_file) = config_file {
+            loader = loader.load_file(config_file)?;
+        } else if let Some(config_file) = self.config.env.var(&config_file_var) {
+            loader = loader.load_file(config_file)?;
+        }
+        if loader.config.env_overrides.is_none() {
+            loader = loader.env_overrides("CONFIG_FORCE_");
+        }
+        Ok(loader)
+    }
+
+    /// Load the classpath resources with this name, if they exist
+    fn load_classpath(self, name: &str) -> Result<Self, Error> {
+        self.with_read_log(|loader| {
+            match internals::HoconInternal::resolve_classpath(name, &loader.config.without_file()) {
+                Ok(Some(internal)) => Ok(Self {
+                    internal: loader.internal.add(internal),
+                    ..loader
+                }),
+                Ok(None) => Ok(loader),
+                Err(error) => loader.collect_error(error),
+            }
+        })
+    }
+
+    /// Load every `.conf`, `.json` and `.properties` file of a directory, in the order of
+    /// their names: values of a file override the ones of the files before it. Files can
+    /// also be included with a glob pattern, like `include "conf.d/*.conf"`
+    ///
+    /// A file named like another file of the directory with a `.<profile>` suffix, like
+    /// `name.prod.conf` next to `name.conf`, is a profile overlay: it is only loaded with its
+    /// base file if the profile was set with
+    /// [`with_profile`](struct.HoconLoader.html#method.with_profile)
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_dir("tests/data/conf.d")?.hocon()?;
+    ///
+    /// assert_eq!(doc["app"]["port"].as_i64(), Some(9090));
+    /// assert_eq!(doc["app"]["name"].as_string(), Some(String::from("override")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::File`](enum.Error.html#variant.File) if the directory can't be read
+    /// * the errors of [`load_file`](struct.HoconLoader.html#method.load_file) for each file
+    pub fn load_dir<P: AsRef<Path>>(&self, dir: P) -> Result<Self, Error> {
+        let dir_path = Self::absolute_path(&dir)?;
+        let files = include::matching_files(&dir_path, "*");
+        let mut loader = self.clone();
+        loader.dependencies.push(Dependency::File {
+            path: dir_path.clone(),
+            found: files.is_ok(),
+        });
+        let files = match files {
+            Ok(files) => files,
+            Err(_) => {
+                return loader.collect_error(Error::File {
+                    path: dir_path.display().to_string(),
+                })
+            }
+        };
+        let stem = |file: &Path| {
+            file.file_stem()
+                .and_then(std::ffi::OsStr::to_str)
+                .map(String::from)
+        };
+        let stems = files
+            .iter()
+            .filter_map(|file| stem(file))
+            .collect::<std::collections::HashSet<_>>();
+        // overlays of profiles are loaded with their base file
+        let is_overlay = |file: &Path| {
+            stem(file)
+                .and_then(|stem| stem.rsplit_once('.').map(|(base, _)| stems.contains(base)))
+                .unwrap_or(false)
+        };
+        files
+            .iter()
+            .filter(|file| !is_overlay(file))
+            .try_fold(loader, |loader, file| loader.load_file(file))
+    }
+
+    /// Every file and URL read by the `load_*` calls, in the order they were read. This
+    /// includes the files of `include` directives, the `.conf`, `.json` and `.properties`
+    /// files looked for when loading a path without extension, the profile overlays looked
+    /// for, the directories listed by [`load_dir`](struct.HoconLoader.html#method.load_dir)
+    /// and glob includes, and the files that were not found
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Dependency, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let loader = HoconLoader::new().load_file("tests/data/include.conf")?;
+    ///
+    /// let files = loader
+    ///     .dependencies()
+    ///     .iter()
+    ///     .filter(|dependency| dependency.found())
+    ///     .filter_map(|dependency| dependency.path()?.file_name()?.to_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(files, vec!["include.conf", "substitution.conf", "basic.conf"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
     /// Load the documents as HOCON
     ///
     /// # Errors in strict mode
     ///
     /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
-    /// included file
+    ///   included file, or if a file included with `include required("...")` is missing.
+    ///   Other missing included files are ignored
     /// * [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) if there is a substitution
     /// with a key that is not present in the document
     /// * [`Error::DisabledExternalUrl`](enum.Error.html#variant.DisabledExternalUrl) if crate
     /// was built without feature `url-support` and an `include url("...")` was found
+    ///
+    /// # Errors when collecting errors
+    ///
+    /// * [`Error::Validation`](enum.Error.html#variant.Validation) with all the errors found
+    ///   while loading the documents, if any
     pub fn hocon(self) -> Result<Hocon, Error> {
+        if self.config.collect_errors {
+            return self.hocon_with_origins().map(|(hocon, _)| hocon);
+        }
+        let config = &self.config;
+        Self::with_env_overrides(self.internal, config)
+            .merge(config)?
+            .finalize(config)
+    }
+
+    /// Add the environment overrides after all the loaded documents
+    fn with_env_overrides(
+        internal: internals::HoconInternal,
+        config: &HoconLoaderConfig,
+    ) -> internals::HoconInternal {
+        match config.env_overrides.as_ref() {
+            Some(prefix) => internal.add(internals::HoconInternal::from_env_overrides(
+                prefix,
+                config.env.vars(),
+            )),
+            None => internal,
+        }
+    }
+
+    /// Load the documents as HOCON, along with the [`Origins`](struct.Origins.html) of
+    /// all of their values: the file or URL and line where each value was defined, and the
+    /// `include` directives that brought it in
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let (doc, origins) = HoconLoader::new()
+    ///     .load_str("a = 5\nb { c = 7 }")?
+    ///     .hocon_with_origins()?;
+    ///
+    /// assert_eq!(doc["b"]["c"].as_i64(), Some(7));
+    /// assert_eq!(origins.get(&["b", "c"]).and_then(|origin| origin.line()), Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors in strict mode
+    ///
+    /// Same as [`hocon`](struct.HoconLoader.html#method.hocon)
+    pub fn hocon_with_origins(self) -> Result<(Hocon, Origins), Error> {
         let config = &self.config;
-        self.internal.merge(config)?.finalize(config)
+        let intermediate = Self::with_env_overrides(self.internal, config).merge(config)?;
+        let origins = intermediate.origins();
+        let hocon = intermediate.finalize(config)?;
+        if config.collect_errors {
+            let errors = Self::diagnostics(self.errors, &hocon, &origins);
+            if !errors.is_empty() {
+                return Err(Error::Validation { errors });
+            }
+        }
+        Ok((hocon, origins))
+    }
+
+    /// Load the documents as HOCON, along with a [`Diagnostic`](struct.Diagnostic.html) for
+    /// every [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) of the document, with its
+    /// path and origin. This lets a lenient load report what went wrong
+    ///
+    /// Includes that failed don't add any value to the document, they come first with an
+    /// empty path and the origin of the `include` directive
+    ///
+    /// When [collecting errors](struct.HoconLoader.html#method.collect_errors), the errors
+    /// found while loading the documents are returned first, instead of an
+    /// [`Error::Validation`](enum.Error.html#variant.Validation)
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let (doc, diagnostics) = HoconLoader::new()
+    ///     .no_system()
+    ///     .load_str("a = 5\nb { c = ${missing} }")?
+    ///     .hocon_with_diagnostics()?;
+    ///
+    /// assert_eq!(doc["a"].as_i64(), Some(5));
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].path(), "b.c");
+    /// assert_eq!(diagnostics[0].error(), &Error::KeyNotFound { key: String::from("missing") });
+    /// assert_eq!(diagnostics[0].origin().and_then(|origin| origin.line()), Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors in strict mode
+    ///
+    /// Same as [`hocon`](struct.HoconLoader.html#method.hocon)
+    pub fn hocon_with_diagnostics(self) -> Result<(Hocon, Vec<Diagnostic>), Error> {
+        let config = &self.config;
+        let intermediate = Self::with_env_overrides(self.internal, config).merge(config)?;
+        let origins = intermediate.origins();
+        let hocon = intermediate.finalize(config)?;
+        let diagnostics = Self::diagnostics(self.errors, &hocon, &origins);
+        Ok((hocon, diagnostics))
     }
 
     /// Deserialize the loaded documents to the target type
@@ -508,49 +1220,86 @@ impl HoconLoader {
     }
 }
 
+/// Load the standard documents of an application, like `ConfigFactory.load()` of Typesafe
+/// Config, with the default [`HoconLoader`](struct.HoconLoader.html): `reference` and
+/// `application` documents, then the file named by the `--config-file` command line flag or
+/// the `<APP_NAME>_CONFIG_FILE` environment variable, then the `CONFIG_FORCE_` environment
+/// overrides. See [`HoconLoader::load_default`](struct.HoconLoader.html#method.load_default)
+pub fn load_default(app_name: &str) -> Result<Hocon, Error> {
+    let config_file = config_file_flag(std::env::args().skip(1));
+    HoconLoader::new()
+        .load_default(app_name, config_file.as_ref().map(Path::new))?
+        .hocon()
+}
+
+/// Value of the `--config-file` command line flag among these arguments
+fn config_file_flag(mut args: impl Iterator<Item = String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--config-file" {
+            return args.next();
+        }
+        if let Some(config_file) = arg.strip_prefix("--config-file=") {
+            return Some(String::from(config_file));
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ConfFileMeta, Hocon, HoconLoader, HoconLoaderConfig};
-    use std::path::Path;
+    use super::{Error, Format, Hocon, HoconLoader};
+
+    #[test]
+    fn config_file_from_args() {
+        let args =
+            |args: &[&str]| super::config_file_flag(args.iter().map(|arg| String::from(*arg)));
+        assert_eq!(
+            args(&["--config-file", "a.conf"]),
+            Some(String::from("a.conf"))
+        );
+        assert_eq!(
+            args(&["-v", "--config-file=b.conf"]),
+            Some(String::from("b.conf"))
+        );
+        assert_eq!(args(&["--config-file"]), None);
+        assert_eq!(args(&["config-file.conf"]), None);
+    }
 
     #[test]
     fn read_from_properties() {
-        let s = r#"a.b:c"#;
-        let loader = dbg!(HoconLoader {
-            config: HoconLoaderConfig {
-                file_meta: Some(ConfFileMeta::from_path(
-                    Path::new("file.properties").to_path_buf()
-                )),
-                ..Default::default()
-            },
-            ..Default::default()
-        }
-        .load_str(s));
+        let s = "a.b:c\nd = e f";
+        let loader = dbg!(HoconLoader::new().load_properties_str(s));
         assert!(loader.is_ok());
 
         let doc = loader.expect("during test").hocon().expect("during test");
         assert_eq!(doc["a"]["b"].as_string(), Some(String::from("c")));
+        assert_eq!(doc["d"].as_string(), Some(String::from("e f")));
     }
 
     #[test]
     fn read_from_hocon() {
         let s = r#"a.b:c"#;
-        let loader = dbg!(HoconLoader {
-            config: HoconLoaderConfig {
-                file_meta: Some(ConfFileMeta::from_path(
-                    Path::new("file.conf").to_path_buf()
-                )),
-                ..Default::default()
-            },
-            ..Default::default()
-        }
-        .load_str(s));
+        let loader = dbg!(HoconLoader::new().load_str_as(Format::Hocon, s));
         assert!(loader.is_ok());
 
         let doc: Hocon = loader.expect("during test").hocon().expect("during test");
         assert_eq!(doc["a"]["b"].as_string(), Some(String::from("c")));
     }
 
+    #[test]
+    fn read_from_json() {
+        let loader = dbg!(HoconLoader::new().load_json_str(r#"{"a": {"b": [1, "c"]}}"#));
+        assert!(loader.is_ok());
+
+        let doc: Hocon = loader.expect("during test").hocon().expect("during test");
+        assert_eq!(doc["a"]["b"][1].as_string(), Some(String::from("c")));
+
+        assert!(matches!(
+            HoconLoader::new().strict().load_json_str(r#"{"a": }"#),
+            Err(Error::Parse { line: 1, .. })
+        ));
+    }
+
     use serde::Deserialize;
 
     #[derive(Deserialize, Debug)]
@@ -658,20 +1407,18 @@ mod tests {
     #[cfg(feature = "url-support")]
     #[test]
     fn can_disable_url_include() {
-        let doc = dbg!(HoconLoader::new()
+        let (doc, diagnostics) = dbg!(HoconLoader::new()
             .no_url_include()
             .load_file("tests/data/include_url.conf")
             .unwrap()
-            .hocon())
+            .hocon_with_diagnostics())
         .unwrap();
         assert_eq!(doc["d"], Hocon::BadValue(super::Error::MissingKey));
         assert_eq!(
-            doc["https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf"],
-            Hocon::BadValue(
-                super::Error::Include {
-                    path: String::from("https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf")
-                }
-            )
+            diagnostics[0].error(),
+            &Error::Include {
+                path: String::from("https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf")
+            }
         );
     }
 }
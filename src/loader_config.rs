@@ -3,6 +3,32 @@ use std::ffi::OsStr;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::include::Classpath;
+use crate::{Dependency, Diagnostic, EnvSource, IncludeResolver, Origin, SystemEnv};
+
+/// Format of a document to load
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// An HOCON document
+    Hocon,
+    /// A JSON document
+    Json,
+    /// A Java `.properties` document
+    Properties,
+}
+
+impl Format {
+    /// Format of a document, guessed from the extension of its path
+    pub(crate) fn of_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(OsStr::to_str) {
+            Some("json") => Format::Json,
+            Some("properties") => Format::Properties,
+            _ => Format::Hocon,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub(crate) enum FileType {
@@ -19,29 +45,33 @@ pub(crate) struct FileRead {
     pub(crate) hocon: Option<String>,
 }
 impl FileRead {
-    fn from_file_type(ft: &FileType, s: String) -> Self {
-        match ft {
-            FileType::Properties => Self {
+    pub(crate) fn from_format(format: Format, s: String) -> Self {
+        match format {
+            Format::Properties => Self {
                 properties: Some(s),
                 ..Default::default()
             },
-            FileType::Json => Self {
+            Format::Json => Self {
                 json: Some(s),
                 ..Default::default()
             },
-            FileType::Hocon => Self {
+            Format::Hocon => Self {
                 hocon: Some(s),
                 ..Default::default()
             },
-            FileType::All => unimplemented!(),
         }
     }
+
+    /// No document was found
+    pub(crate) fn is_empty(&self) -> bool {
+        self.properties.is_none() && self.json.is_none() && self.hocon.is_none()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct ConfFileMeta {
     path: PathBuf,
-    full_path: PathBuf,
+    pub(crate) full_path: PathBuf,
     file_type: FileType,
 }
 impl ConfFileMeta {
@@ -75,7 +105,24 @@ pub(crate) struct HoconLoaderConfig {
     #[cfg(feature = "url-support")]
     pub(crate) external_url: bool,
     pub(crate) strict: bool,
+    pub(crate) collect_errors: bool,
     pub(crate) max_include_depth: u8,
+    pub(crate) url: Option<String>,
+    pub(crate) include_origin: Option<Origin>,
+    pub(crate) source: Option<Arc<Source>>,
+    pub(crate) include_resolver: Option<Arc<dyn IncludeResolver>>,
+    pub(crate) classpath: Arc<Classpath>,
+    pub(crate) include_paths: Vec<PathBuf>,
+    pub(crate) env_overrides: Option<String>,
+    pub(crate) env: Arc<dyn EnvSource>,
+    pub(crate) profiles: Vec<String>,
+    pub(crate) read_log: Arc<Mutex<Vec<Dependency>>>,
+    /// Includes that failed when not in strict mode, reported as errors of the whole document
+    pub(crate) include_errors: Arc<Mutex<Vec<Diagnostic>>>,
+    /// Name of the classpath resource being parsed, its includes are resolved in the classpath
+    pub(crate) classpath_resource: Option<String>,
+    /// Parse the syntax of the documents only, without resolving their includes
+    pub(crate) skip_includes: bool,
 }
 
 impl Default for HoconLoaderConfig {
@@ -87,7 +134,21 @@ impl Default for HoconLoaderConfig {
             #[cfg(feature = "url-support")]
             external_url: true,
             strict: false,
+            collect_errors: false,
             max_include_depth: 10,
+            url: None,
+            include_origin: None,
+            source: None,
+            include_resolver: None,
+            classpath: Arc::new(Classpath::default()),
+            include_paths: vec![],
+            env_overrides: None,
+            env: Arc::new(SystemEnv),
+            profiles: vec![],
+            read_log: Arc::default(),
+            include_errors: Arc::default(),
+            classpath_resource: None,
+            skip_includes: false,
         }
     }
 }
@@ -104,15 +165,60 @@ impl HoconLoaderConfig {
         match self.file_meta.as_ref() {
             Some(file_meta) => Self {
                 file_meta: Some(ConfFileMeta::from_path(file_meta.clone().path.join(path))),
+                url: None,
+                source: None,
+                classpath_resource: None,
                 ..self.clone()
             },
             None => Self {
                 file_meta: Some(ConfFileMeta::from_path(path)),
+                url: None,
+                source: None,
+                classpath_resource: None,
                 ..self.clone()
             },
         }
     }
 
+    /// Configuration to load a document from a string, not attached to a file
+    pub(crate) fn without_file(&self) -> Self {
+        Self {
+            file_meta: None,
+            url: None,
+            source: None,
+            classpath_resource: None,
+            ..self.clone()
+        }
+    }
+
+    /// Configuration to load a document included by a directive at this position
+    pub(crate) fn include_at(&self, remaining: usize) -> Self {
+        Self {
+            include_origin: Some(self.origin_at(remaining)),
+            ..self.clone()
+        }
+    }
+
+    /// Origin of a value at this position in the document being parsed
+    pub(crate) fn origin_at(&self, remaining: usize) -> Origin {
+        match self.source.as_ref() {
+            Some(source) => Origin::new(
+                source.name.clone(),
+                Some(source.line_and_column(None, remaining).0),
+                self.include_origin.clone(),
+            ),
+            None => self.document_origin(None),
+        }
+    }
+
+    fn document_origin(&self, extension: Option<&str>) -> Origin {
+        Origin::new(
+            self.source_name(extension.unwrap_or("conf")),
+            None,
+            self.include_origin.clone(),
+        )
+    }
+
     pub(crate) fn parse_str_to_internal(
         &self,
         s: FileRead,
@@ -122,50 +228,74 @@ impl HoconLoaderConfig {
             internal = internal.add(
                 java_properties::read(properties.as_bytes())
                     .map(crate::internals::HoconInternal::from_properties)
-                    .map_err(|_| crate::Error::Parse)?,
+                    .map(|parsed| parsed.with_origin(|| self.document_origin(Some("properties"))))
+                    .map_err(|err| Error::Parse {
+                        path: self.source_name("properties"),
+                        line: err.line_number().unwrap_or(1),
+                        column: 1,
+                        message: err.to_string(),
+                    })?,
             );
         };
         if let Some(json) = s.json {
-            internal = internal.add(
-                crate::parser::root(format!("{}\n\0", json.replace('\r', "\n")).as_bytes(), self)
-                    .map_err(|_| crate::Error::Parse)
-                    .and_then(|(remaining, parsed)| {
-                        if Self::remaining_only_whitespace(remaining) {
-                            parsed
-                        } else if self.strict {
-                            Err(crate::Error::Deserialization {
-                                message: String::from("file could not be parsed completely"),
-                            })
-                        } else {
-                            parsed
-                        }
-                    })?,
-            );
+            internal = internal.add(self.parse_hocon(&json, "json")?);
         };
         if let Some(hocon) = s.hocon {
-            internal = internal.add(
-                crate::parser::root(
-                    format!("{}\n\0", hocon.replace('\r', "\n")).as_bytes(),
-                    self,
-                )
-                .map_err(|_| crate::Error::Parse)
-                .and_then(|(remaining, parsed)| {
-                    if Self::remaining_only_whitespace(remaining) {
-                        parsed
-                    } else if self.strict {
-                        Err(crate::Error::Deserialization {
-                            message: String::from("file could not be parsed completely"),
-                        })
-                    } else {
-                        parsed
-                    }
-                })?,
-            );
+            internal = internal.add(self.parse_hocon(&hocon, "conf")?);
         };
 
         Ok(internal)
     }
 
+    fn parse_hocon(
+        &self,
+        s: &str,
+        extension: &str,
+    ) -> Result<crate::internals::HoconInternal, Error> {
+        let input = format!("{}\n\0", s.replace('\r', "\n"));
+        let source = Arc::new(Source::new(self.source_name(extension), s, input.len()));
+        let config = Self {
+            source: Some(Arc::clone(&source)),
+            ..self.clone()
+        };
+        let parse_error = |remaining: &[u8]| {
+            let (failed_at, message) = crate::parser::locate_error(remaining, &config);
+            let (line, column) = source.line_and_column(Some(s), failed_at.len());
+            Error::Parse {
+                path: source.name.clone(),
+                line,
+                column,
+                message,
+            }
+        };
+        match crate::parser::root(input.as_bytes(), &config) {
+            Ok((remaining, parsed)) => {
+                if Self::remaining_only_whitespace(remaining)
+                    || !(self.strict || self.collect_errors)
+                {
+                    parsed.map(|parsed| parsed.with_origin(|| config.origin_at(input.len())))
+                } else {
+                    Err(parse_error(remaining))
+                }
+            }
+            Err(_) => Err(parse_error(input.as_bytes())),
+        }
+    }
+
+    /// Name of the document being parsed, to be used in errors and origins
+    fn source_name(&self, extension: &str) -> Option<String> {
+        if let Some(url) = self.url.as_ref() {
+            return Some(url.clone());
+        }
+        self.file_meta.as_ref().map(|file_meta| {
+            let mut path = file_meta.full_path.clone();
+            if let FileType::All = file_meta.file_type {
+                path.set_extension(extension);
+            }
+            path.display().to_string()
+        })
+    }
+
     fn remaining_only_whitespace(remaining: &[u8]) -> bool {
         remaining
             .iter()
@@ -178,87 +308,200 @@ impl HoconLoaderConfig {
             .unwrap_or(true)
     }
 
-    pub(crate) fn read_file_to_string(path: PathBuf) -> Result<String, Error> {
+    pub(crate) fn read_file_to_string(path: PathBuf) -> std::io::Result<String> {
         let mut file = File::open(path.as_os_str())?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
         Ok(contents)
     }
 
-    pub(crate) fn read_file(&self) -> Result<FileRead, Error> {
-        let full_path = self
-            .file_meta
-            .clone()
-            .expect("missing file metadata")
-            .full_path;
-        match self.file_meta.as_ref().map(|fm| &fm.file_type) {
-            Some(FileType::All) => Ok(FileRead {
-                hocon: Self::read_file_to_string({
-                    let mut path = full_path.clone();
-                    if !path.exists() {
-                        path.set_extension("conf");
-                    }
-                    path
-                })
-                .ok(),
-                json: Self::read_file_to_string({
-                    let mut path = full_path.clone();
-                    path.set_extension("json");
-                    path
-                })
-                .ok(),
-                properties: Self::read_file_to_string({
-                    let mut path = full_path;
-                    path.set_extension("properties");
-                    path
-                })
-                .ok(),
+    /// Record a file or URL that was read, or probed, while loading documents
+    pub(crate) fn log_read(&self, dependency: Dependency) {
+        if let Ok(mut read_log) = self.read_log.lock() {
+            read_log.push(dependency);
+        }
+    }
+
+    /// Keep the error of an include directive that failed, with the origin of the directive
+    pub(crate) fn log_include_error(&self, error: Error) {
+        if let Ok(mut include_errors) = self.include_errors.lock() {
+            include_errors.push(Diagnostic::new(
+                String::new(),
+                error,
+                self.include_origin.clone(),
+            ));
+        }
+    }
+
+    /// Read a file, `None` if it doesn't exist. Any other error reading it, like a permission
+    /// issue or invalid UTF-8 content, is returned as an `Error::File`
+    fn read_file_if_exists(&self, path: PathBuf) -> Result<Option<String>, Error> {
+        let read = Self::read_file_to_string(path.clone());
+        self.log_read(Dependency::File {
+            path: path.clone(),
+            found: read.is_ok(),
+        });
+        match read {
+            Ok(contents) => Ok(Some(contents)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) => Err(Error::File {
+                path: path.display().to_string(),
             }),
-            Some(ft) => Ok(FileRead::from_file_type(
-                ft,
-                Self::read_file_to_string(full_path)?,
-            )),
-            _ => unimplemented!(),
         }
     }
 
-    #[cfg(feature = "url-support")]
-    pub(crate) fn load_url(&self, url: &str) -> Result<crate::internals::HoconInternal, Error> {
-        if let Ok(parsed_url) = reqwest::Url::parse(url) {
-            if parsed_url.scheme() == "file" {
-                if let Ok(path) = parsed_url.to_file_path() {
-                    let include_config = self.included_from().with_file(path);
-                    let s = include_config.read_file()?;
-                    Ok(include_config.parse_str_to_internal(s).map_err(|_| {
-                        crate::Error::Include {
-                            path: String::from(url),
+    /// Read the file of this configuration. A path without extension is read as `.conf`,
+    /// `.json` and `.properties` files, skipping the ones that don't exist
+    pub(crate) fn read_file(&self) -> Result<FileRead, Error> {
+        match self.read_existing_file()? {
+            Some(read) => Ok(read),
+            None => match self.file_meta.as_ref() {
+                Some(ConfFileMeta {
+                    file_type: FileType::All,
+                    ..
+                }) => Ok(FileRead::default()),
+                file_meta => Err(Error::File {
+                    path: file_meta
+                        .map(|file_meta| file_meta.full_path.display().to_string())
+                        .unwrap_or_default(),
+                }),
+            },
+        }
+    }
+
+    /// Read the file of this configuration as `read_file` does, `None` if no
+    /// file exists
+    pub(crate) fn read_existing_file(&self) -> Result<Option<FileRead>, Error> {
+        let file_meta = self
+            .file_meta
+            .as_ref()
+            .ok_or(Error::IncludeNotAllowedFromStr)?;
+        let full_path = file_meta.full_path.clone();
+        let format = match file_meta.file_type {
+            FileType::Properties => Format::Properties,
+            FileType::Json => Format::Json,
+            FileType::Hocon => Format::Hocon,
+            FileType::All => {
+                let read = FileRead {
+                    hocon: self.read_file_if_exists({
+                        let mut path = full_path.clone();
+                        if !path.exists() {
+                            path.set_extension("conf");
                         }
-                    })?)
-                } else {
-                    Err(crate::Error::Include {
-                        path: String::from(url),
-                    })
+                        path
+                    })?,
+                    json: self.read_file_if_exists({
+                        let mut path = full_path.clone();
+                        path.set_extension("json");
+                        path
+                    })?,
+                    properties: self.read_file_if_exists({
+                        let mut path = full_path;
+                        path.set_extension("properties");
+                        path
+                    })?,
+                };
+                return Ok(if read.is_empty() { None } else { Some(read) });
+            }
+        };
+        Ok(self
+            .read_file_if_exists(full_path)?
+            .map(|contents| FileRead::from_format(format, contents)))
+    }
+
+    /// Load the document at this URL, or `None` if there is no document at this URL
+    #[cfg(feature = "url-support")]
+    pub(crate) fn load_url(
+        &self,
+        url: &str,
+    ) -> Result<Option<crate::internals::HoconInternal>, Error> {
+        let url_error = || Error::Include {
+            path: String::from(url),
+        };
+        let parsed_url = reqwest::Url::parse(url).map_err(|_| url_error())?;
+        if parsed_url.scheme() == "file" {
+            let path = parsed_url.to_file_path().map_err(|_| url_error())?;
+            let include_config = self.included_from().with_file(path);
+            include_config
+                .read_existing_file()?
+                .map(|s| include_config.parse_str_to_internal(s))
+                .transpose()
+        } else if self.external_url {
+            let response = reqwest::blocking::get(parsed_url);
+            self.log_read(Dependency::Url {
+                url: String::from(url),
+                found: matches!(&response, Ok(response) if response.status().is_success()),
+            });
+            let response = response.map_err(|_| url_error())?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let body = response
+                .error_for_status()
+                .and_then(reqwest::blocking::Response::text)
+                .map_err(|_| url_error())?;
+
+            Ok(Some(
+                Self {
+                    url: Some(String::from(url)),
+                    ..self.clone()
                 }
-            } else if self.external_url {
-                let body = reqwest::blocking::get(parsed_url)
-                    .and_then(reqwest::blocking::Response::text)
-                    .map_err(|_| crate::Error::Include {
-                        path: String::from(url),
-                    })?;
-
-                Ok(self.parse_str_to_internal(FileRead {
+                .parse_str_to_internal(FileRead {
                     hocon: Some(body),
                     ..Default::default()
-                })?)
-            } else {
-                Err(crate::Error::Include {
-                    path: String::from(url),
-                })
-            }
+                })?,
+            ))
         } else {
-            Err(crate::Error::Include {
-                path: String::from(url),
-            })
+            Err(url_error())
         }
     }
 }
+
+/// A document being parsed, used to find the line of a position in the parser input
+#[derive(Debug)]
+pub(crate) struct Source {
+    name: Option<String>,
+    input_len: usize,
+    line_starts: Vec<usize>,
+}
+
+impl Source {
+    fn new(name: Option<String>, s: &str, input_len: usize) -> Self {
+        let bytes = s.as_bytes();
+        Self {
+            name,
+            input_len,
+            line_starts: bytes
+                .iter()
+                .enumerate()
+                .filter(|(idx, c)| {
+                    **c == b'\n' || (**c == b'\r' && bytes.get(idx + 1) != Some(&b'\n'))
+                })
+                .map(|(idx, _)| idx + 1)
+                .collect(),
+        }
+    }
+
+    /// Line and column, starting at 1, of the position where `remaining` bytes of the
+    /// parser input are left. The column can only be computed with the document content
+    fn line_and_column(&self, s: Option<&str>, remaining: usize) -> (usize, usize) {
+        let offset = self.input_len.saturating_sub(remaining);
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let column = s
+            .map(|s| {
+                let line_start = if line == 0 {
+                    0
+                } else {
+                    self.line_starts[line - 1]
+                };
+                let bytes = s.as_bytes();
+                1 + String::from_utf8_lossy(
+                    &bytes[line_start.min(bytes.len())..offset.min(bytes.len())],
+                )
+                .chars()
+                .count()
+            })
+            .unwrap_or(1);
+        (line + 1, column)
+    }
+}
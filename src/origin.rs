@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use linked_hash_map::LinkedHashMap;
+
+/// Where a value of an HOCON document was defined
+///
+/// An `Origin` knows the file or URL a value was read from, its line, and the `include`
+/// directive that brought this document in, if any.
+///
+/// # Usage
+///
+/// ```rust
+/// # use hocon::{HoconLoader, Error, Origin};
+/// # fn main() -> Result<(), Error> {
+/// let (_doc, origins) = HoconLoader::new()
+///     .load_file("tests/data/include.conf")?
+///     .hocon_with_origins()?;
+///
+/// let origin = origins.get(&["b"]).expect("b is defined in an included file");
+/// assert!(origin.path().expect("b was read from a file").ends_with("basic.conf"));
+/// assert_eq!(origin.line(), Some(3));
+/// assert_eq!(origin.included_from().and_then(Origin::line), Some(1));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Origin {
+    path: Option<String>,
+    line: Option<usize>,
+    included_from: Option<Box<Origin>>,
+}
+
+impl Origin {
+    pub(crate) fn new(
+        path: Option<String>,
+        line: Option<usize>,
+        included_from: Option<Origin>,
+    ) -> Self {
+        Self {
+            path,
+            line,
+            included_from: included_from.map(Box::new),
+        }
+    }
+
+    /// Path of the file or URL the value was read from, `None` if it was loaded from a string
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Line where the value was defined, starting at 1. `None` for formats without line
+    /// information, like `.properties` files
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Origin of the `include` directive that included the document the value was read from
+    pub fn included_from(&self) -> Option<&Origin> {
+        self.included_from.as_deref()
+    }
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.path.as_deref().unwrap_or("string"))?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        if let Some(included_from) = &self.included_from {
+            write!(f, " via {}", included_from)?;
+        }
+        Ok(())
+    }
+}
+
+/// Origins of all the values of an HOCON document, as returned by
+/// [`HoconLoader::hocon_with_origins`](struct.HoconLoader.html#method.hocon_with_origins)
+///
+/// Values are identified by their path in the document, as a list of keys. Elements of
+/// an array are identified by their index.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Origins {
+    origins: LinkedHashMap<Vec<String>, Origin>,
+}
+
+impl Origins {
+    pub(crate) fn insert(&mut self, path: Vec<String>, origin: Origin) {
+        self.origins.insert(path, origin);
+    }
+
+    /// Get the origin of the value at this path
+    pub fn get<S: AsRef<str>>(&self, path: &[S]) -> Option<&Origin> {
+        self.origins.get(
+            &path
+                .iter()
+                .map(|key| String::from(key.as_ref()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Iterate over the paths of all the values with a known origin
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec<String>, &Origin)> {
+        self.origins.iter()
+    }
+}
+
+/// A file or URL read while loading documents, as returned by
+/// [`HoconLoader::dependencies`](struct.HoconLoader.html#method.dependencies)
+///
+/// Files that were looked for but not found are dependencies too, as creating them would
+/// change the loaded document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dependency {
+    /// A file, or a directory searched for a glob include
+    File {
+        /// Path of the file
+        path: PathBuf,
+        /// The file was found
+        found: bool,
+    },
+    /// An URL
+    Url {
+        /// The URL
+        url: String,
+        /// The URL was found
+        found: bool,
+    },
+}
+
+impl Dependency {
+    /// The file or URL was found
+    pub fn found(&self) -> bool {
+        match self {
+            Dependency::File { found, .. } | Dependency::Url { found, .. } => *found,
+        }
+    }
+
+    /// Path of the file, `None` for an URL
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Dependency::File { path, .. } => Some(path),
+            Dependency::Url { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_origin() {
+        let origin = Origin::new(
+            Some(String::from("/etc/app/override.conf")),
+            Some(12),
+            Some(Origin::new(
+                Some(String::from("application.conf")),
+                Some(3),
+                None,
+            )),
+        );
+
+        assert_eq!(
+            origin.to_string(),
+            "/etc/app/override.conf:12 via application.conf:3"
+        );
+        assert_eq!(
+            Origin::new(Some(String::from("app.properties")), None, None).to_string(),
+            "app.properties"
+        );
+        assert_eq!(Origin::new(None, Some(1), None).to_string(), "string:1");
+    }
+}
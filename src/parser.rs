@@ -3,8 +3,9 @@ use nom::*;
 use std::borrow::Cow;
 use std::str;
 
-use crate::internals::{unescape, Hash, HoconInternal, HoconValue, Include};
+use crate::internals::{unescape, Hash, HoconInternal, HoconValue};
 use crate::HoconLoaderConfig;
+use crate::Include;
 
 named!(
     space<()>,
@@ -52,6 +53,19 @@ named!(
     ))
 );
 
+/// Position of the next token, as the length of the input remaining from there.
+/// This doesn't consume anything
+fn position(input: &[u8]) -> IResult<&[u8], usize> {
+    let blanks = input.iter().take_while(|c| c.is_ascii_whitespace()).count();
+    Ok((input, input.len() - blanks))
+}
+
+fn with_origin(hash: Hash, config: &HoconLoaderConfig, at: usize) -> Hash {
+    HoconInternal { internal: hash }
+        .with_origin(|| config.origin_at(at))
+        .internal
+}
+
 named!(integer<i64>, flat_map!(recognize_float, parse_to!(i64)));
 
 named!(
@@ -262,8 +276,9 @@ named_args!(
     key_value<'a>(config: &HoconLoaderConfig)<Result<Hash, crate::Error>>,
     do_parse!(
         ws!(possible_comment)
+            >> at: position
             >> pair: sp!(alt!(
-                call!(include) => { |path| Ok(HoconInternal::from_include(path, config)?.internal) } |
+                call!(include) => { |(included, required)| Ok(HoconInternal::from_include(included, required, &config.include_at(at))?.internal) } |
                 separated_pair!(ws!(string), ws!(alt!(char!(':') | char!('='))), call!(wrapper, config))
                     => { |(s, h): (Cow<str>, Result<HoconInternal, _>)|
                         Ok(HoconInternal::from_object(h?.internal)
@@ -317,7 +332,7 @@ named_args!(
                         }
                     }
             ))
-            >> (pair)
+            >> (pair.map(|hash| with_origin(hash, config, at)))
     )
 );
 
@@ -360,7 +375,7 @@ named_args!(
                 Ok(values)
             }
             (Some(subst), _) => {
-                let mut values = vec![(vec![], HoconValue::PathSubstitution{target: Box::new(subst), optional: false, original: None})];
+                let mut values = vec![(vec![], HoconValue::PathSubstitution{target: Box::new(subst), optional: false, original: None}, None)];
                 values.append(&mut first_hash?);
                 crate::helper::extract_result(remaining_hashes)?.into_iter().for_each(|mut hash| values.append(&mut hash));
                 Ok(values)
@@ -421,22 +436,43 @@ named!(
 );
 
 named!(
-    include<Include>,
+    include_target<Include>,
+    alt!(
+        map!(call!(string), |name| Include::Name(name.into_owned()))
+            | do_parse!(
+                tag!("file(")
+                    >> file_name: string
+                    >> tag!(")")
+                    >> (Include::File(file_name.into_owned()))
+            )
+            | do_parse!(
+                tag!("url(")
+                    >> url: string
+                    >> tag!(")")
+                    >> (Include::Url(url.into_owned()))
+            )
+            | do_parse!(
+                tag!("classpath(")
+                    >> resource: string
+                    >> tag!(")")
+                    >> (Include::Classpath(resource.into_owned()))
+            )
+    )
+);
+
+named!(
+    include<(Include, bool)>,
     do_parse!(
         tag!("include ")
             >> ws!(many0!(newline))
             >> included:
                 sp!(alt!(
-                    map!(call!(string), Include::File)
-                        | do_parse!(
-                            tag!("file(")
-                                >> file_name: string
-                                >> tag!(")")
-                                >> (Include::File(file_name))
-                        )
-                        | do_parse!(
-                            tag!("url(") >> url: string >> tag!(")") >> (Include::Url(url))
-                        )
+                    do_parse!(
+                        tag!("required(")
+                            >> included: sp!(include_target)
+                            >> tag!(")")
+                            >> ((included, true))
+                    ) | map!(include_target, |included| (included, false))
                 ))
             >> (included)
     )
@@ -445,8 +481,8 @@ named!(
 named_args!(
     root_include<'a>(config: &HoconLoaderConfig)<Result<HoconInternal, crate::Error>>,
     map!(
-        do_parse!(file_name: ws!(include) >> doc: call!(root, config) >> ((file_name, doc))),
-        |(included, doc)| doc?.add_include(included, config)
+        do_parse!(at: position >> file_name: ws!(include) >> doc: call!(root, config) >> ((at, file_name, doc))),
+        |(at, (included, required), doc)| doc?.add_include(included, required, &config.include_at(at))
     )
 );
 
@@ -454,14 +490,15 @@ named_args!(
     wrapper<'a>(config: &HoconLoaderConfig)<Result<HoconInternal, crate::Error>>,
     do_parse!(
         possible_comment
+            >> at: position
             >> wrapped:
                 alt!(
-                    call!(hashes, config) => { |h| Ok(HoconInternal::from_object(h?))     } |
-                    call!(arrays, config) => { |a| Ok(HoconInternal::from_array(a?))      } |
-                    include               => { |f| HoconInternal::from_include(f, config) } |
-                    value                 => { |v| Ok(HoconInternal::from_value(v))       }
+                    call!(hashes, config) => { |h| Ok(HoconInternal::from_object(h?))                        } |
+                    call!(arrays, config) => { |a| Ok(HoconInternal::from_array(a?))                         } |
+                    include               => { |(f, r)| HoconInternal::from_include(f, r, &config.include_at(at)) } |
+                    value                 => { |v| Ok(HoconInternal::from_value(v))                          }
                 )
-            >> (wrapped)
+            >> (wrapped.map(|wrapped| wrapped.with_origin(|| config.origin_at(at))))
     )
 );
 
@@ -480,3 +517,153 @@ named_args!(
             >> (wrapped)
     )
 );
+
+fn skip_blanks(mut input: &[u8]) -> &[u8] {
+    loop {
+        if let Ok((remaining, _)) = space(input) {
+            input = remaining;
+        }
+        match input.first() {
+            Some(b'\n') | Some(b',') => input = &input[1..],
+            Some(b'#') => input = skip_line(input),
+            Some(b'/') if input.starts_with(b"//") => input = skip_line(input),
+            _ => return input,
+        }
+    }
+}
+
+fn skip_line(input: &[u8]) -> &[u8] {
+    match memchr::memchr(b'\n', input) {
+        Some(idx) => &input[idx..],
+        None => &input[input.len()..],
+    }
+}
+
+fn at_end(input: &[u8]) -> bool {
+    input.iter().all(|c| *c == b'\0')
+}
+
+fn describe_unexpected(input: &[u8], closing: Option<u8>) -> String {
+    match (input.first(), closing) {
+        (None, Some(closing)) | (Some(b'\0'), Some(closing)) => format!(
+            "unexpected end of document, expected '{}'",
+            char::from(closing)
+        ),
+        (None, None) | (Some(b'\0'), None) => String::from("unexpected end of document"),
+        (Some(c), Some(closing)) if *c != closing => format!(
+            "unexpected '{}', expected '{}'",
+            char::from(*c),
+            char::from(closing)
+        ),
+        (Some(c), _) => format!("unexpected '{}'", String::from_utf8_lossy(&[*c])),
+    }
+}
+
+/// Find where parsing of an invalid document stopped, and what was expected there.
+///
+/// nom only reports the start of the alternative that failed, so this walks down into
+/// the objects and arrays of `input` until reaching the first item that cannot be parsed.
+pub(crate) fn locate_error<'a>(input: &'a [u8], config: &HoconLoaderConfig) -> (&'a [u8], String) {
+    // the documents included before the error were already read
+    let config = &HoconLoaderConfig {
+        skip_includes: true,
+        ..config.clone()
+    };
+    let input = skip_blanks(input);
+    match input.first() {
+        Some(b'{') => locate_error_in_object(&input[1..], Some(b'}'), config),
+        Some(b'[') => locate_error_in_array(&input[1..], config),
+        _ => locate_error_in_object(input, None, config),
+    }
+}
+
+fn locate_error_in_object<'a>(
+    input: &'a [u8],
+    closing: Option<u8>,
+    config: &HoconLoaderConfig,
+) -> (&'a [u8], String) {
+    let remaining = match separated_hashlist(skip_blanks(input), config) {
+        Ok((remaining, _)) => skip_blanks(remaining),
+        Err(_) => skip_blanks(input),
+    };
+    match remaining.first() {
+        _ if at_end(remaining) => (remaining, describe_unexpected(remaining, closing)),
+        Some(b'}') | Some(b']') => (remaining, describe_unexpected(remaining, closing)),
+        _ => locate_error_in_key_value(remaining, config),
+    }
+}
+
+/// The input starts with an `include` directive, and not with a key starting with `include`
+fn is_include(input: &[u8]) -> bool {
+    if !input.starts_with(b"include") {
+        return false;
+    }
+    let after = &input[b"include".len()..];
+    match after.first() {
+        Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') | Some(b'"') => true,
+        _ => [&b"file("[..], b"url(", b"classpath(", b"required("]
+            .iter()
+            .any(|target| after.starts_with(target)),
+    }
+}
+
+fn locate_error_in_key_value<'a>(
+    input: &'a [u8],
+    config: &HoconLoaderConfig,
+) -> (&'a [u8], String) {
+    if is_include(input) {
+        return (
+            input,
+            String::from(
+                "expected \"file\", file(\"file\"), url(\"url\"), classpath(\"resource\") or required(...) after include",
+            ),
+        );
+    }
+    let after_key = match string(input) {
+        Ok((remaining, _)) => remaining,
+        Err(_) => match unquoted_string(input) {
+            Ok((remaining, _)) => remaining,
+            Err(_) => return (input, String::from("expected a key")),
+        },
+    };
+    let after_key = match space(after_key) {
+        Ok((remaining, _)) => remaining,
+        Err(_) => after_key,
+    };
+    match after_key.first() {
+        Some(b':') | Some(b'=') => locate_error_in_value(skip_blanks(&after_key[1..]), config),
+        Some(b'+') if after_key.starts_with(b"+=") => {
+            locate_error_in_value(skip_blanks(&after_key[2..]), config)
+        }
+        Some(b'{') => locate_error_in_value(after_key, config),
+        _ => (after_key, String::from("expected ':' or '=' after key")),
+    }
+}
+
+fn locate_error_in_value<'a>(input: &'a [u8], config: &HoconLoaderConfig) -> (&'a [u8], String) {
+    match input.first() {
+        Some(b'{') => locate_error_in_object(&input[1..], Some(b'}'), config),
+        Some(b'[') => locate_error_in_array(&input[1..], config),
+        Some(b'"') if !input.starts_with(b"\"\"\"") => {
+            (input, String::from("expected closing '\"' for string"))
+        }
+        Some(b'"') => (input, String::from("expected closing '\"\"\"' for string")),
+        _ if at_end(input) => (
+            input,
+            String::from("unexpected end of document, expected a value"),
+        ),
+        _ => (input, String::from("expected a value")),
+    }
+}
+
+fn locate_error_in_array<'a>(input: &'a [u8], config: &HoconLoaderConfig) -> (&'a [u8], String) {
+    let remaining = match separated_list!(skip_blanks(input), separators, call!(wrapper, config)) {
+        Ok((remaining, _)) => skip_blanks(remaining),
+        Err(_) => skip_blanks(input),
+    };
+    match remaining.first() {
+        _ if at_end(remaining) => (remaining, describe_unexpected(remaining, Some(b']'))),
+        Some(b'}') | Some(b']') => (remaining, describe_unexpected(remaining, Some(b']'))),
+        _ => locate_error_in_value(remaining, config),
+    }
+}
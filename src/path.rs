@@ -0,0 +1,108 @@
+use crate::internals::unescape;
+use crate::Error;
+
+/// Characters that can't be used in an unquoted key of a path expression
+const FORBIDDEN: &str = "$\"{}[]:=,+#`^?!@*&\\";
+
+/// Parse an HOCON path expression like `a.b."c.d"` to the list of its keys
+pub(crate) fn parse_path(path: &str) -> Result<Vec<String>, Error> {
+    let bad_path = |message: &str| Error::BadPath {
+        path: String::from(path),
+        message: String::from(message),
+    };
+
+    let mut keys = vec![];
+    let mut key = String::new();
+    let mut has_key = false;
+    let mut chars = path.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if !has_key {
+                    return Err(bad_path("empty key"));
+                }
+                keys.push(std::mem::take(&mut key));
+                has_key = false;
+            }
+            '"' => {
+                let mut quoted = String::new();
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => {
+                            quoted.push(c);
+                            quoted.extend(chars.next());
+                        }
+                        c => quoted.push(c),
+                    }
+                }
+                if !closed {
+                    return Err(bad_path("unterminated quoted key"));
+                }
+                key.push_str(&unescape(&quoted));
+                has_key = true;
+            }
+            c if FORBIDDEN.contains(c) => {
+                return Err(bad_path(&format!(
+                    "'{}' is not allowed in an unquoted key",
+                    c
+                )));
+            }
+            c => {
+                key.push(c);
+                has_key = true;
+            }
+        }
+    }
+    if !has_key {
+        return Err(bad_path("empty key"));
+    }
+    keys.push(key);
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!(parse_path("a"), Ok(vec![String::from("a")]));
+        assert_eq!(
+            parse_path(r#" a.b-c."d.e".f_1 "#),
+            Ok(vec![
+                String::from("a"),
+                String::from("b-c"),
+                String::from("d.e"),
+                String::from("f_1")
+            ])
+        );
+        assert_eq!(
+            parse_path(r#"a."".x"y\"z""#),
+            Ok(vec![
+                String::from("a"),
+                String::from(""),
+                String::from("xy\"z")
+            ])
+        );
+        assert_eq!(
+            parse_path("0.1"),
+            Ok(vec![String::from("0"), String::from("1")])
+        );
+    }
+
+    #[test]
+    fn parse_bad_paths() {
+        for path in &["", "a..b", ".a", "a.", r#"a."b"#, "a.${b}", "a[0]"] {
+            assert!(
+                matches!(parse_path(path), Err(Error::BadPath { .. })),
+                "{} should not be a valid path",
+                path
+            );
+        }
+    }
+}
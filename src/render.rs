@@ -0,0 +1,433 @@
+use std::fmt::Write;
+
+use crate::{Hocon, Origins};
+
+/// Options to render an [`Hocon`](enum.Hocon.html) document as text, with
+/// [`Hocon::render`](enum.Hocon.html#method.render)
+///
+/// By default, documents are rendered as formatted HOCON with unquoted keys when possible.
+///
+/// # Usage
+///
+/// ```rust
+/// # use hocon::{HoconLoader, Error, RenderOptions};
+/// # fn main() -> Result<(), Error> {
+/// let doc = HoconLoader::new().load_str(r#"{ a: { b: 1, c: [x, y] } }"#)?.hocon()?;
+///
+/// assert_eq!(
+///     doc.render(&RenderOptions::new().json().concise()),
+///     r#"{"a":{"b":1,"c":["x","y"]}}"#
+/// );
+/// assert_eq!(
+///     doc.render(&RenderOptions::new()),
+///     "a {\n    b = 1\n    c = [\n        \"x\",\n        \"y\"\n    ]\n}\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    json: bool,
+    formatted: bool,
+    unquoted_keys: bool,
+    origins: Option<Origins>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderOptions {
+    /// New `RenderOptions` rendering formatted HOCON
+    pub fn new() -> Self {
+        Self {
+            json: false,
+            formatted: true,
+            unquoted_keys: true,
+            origins: None,
+        }
+    }
+
+    /// Render as JSON instead of HOCON. Keys are always quoted, and no comments are written
+    pub fn json(&self) -> Self {
+        Self {
+            json: true,
+            ..self.clone()
+        }
+    }
+
+    /// Render on a single line, without indentation nor comments
+    pub fn concise(&self) -> Self {
+        Self {
+            formatted: false,
+            ..self.clone()
+        }
+    }
+
+    /// Always quote keys, even when they could be left unquoted
+    pub fn quoted_keys(&self) -> Self {
+        Self {
+            unquoted_keys: false,
+            ..self.clone()
+        }
+    }
+
+    /// Add a comment with its origin before each value, as returned by
+    /// [`HoconLoader::hocon_with_origins`](struct.HoconLoader.html#method.hocon_with_origins).
+    /// Comments are only written when rendering formatted HOCON
+    pub fn origin_comments(&self, origins: &Origins) -> Self {
+        Self {
+            origins: Some(origins.clone()),
+            ..self.clone()
+        }
+    }
+
+    fn comments(&self) -> bool {
+        self.formatted && !self.json
+    }
+}
+
+impl Hocon {
+    /// Render this document as text, following the given [`RenderOptions`](struct.RenderOptions.html)
+    ///
+    /// A [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) is rendered as `null`, preceded
+    /// by a comment with its error when rendering formatted HOCON
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut renderer = Renderer {
+            options,
+            out: String::new(),
+            path: vec![],
+        };
+        match self {
+            Hocon::Hash(hash) if options.comments() && !hash.is_empty() => {
+                renderer.render_entries(hash, 0);
+            }
+            _ => {
+                renderer.render_value(self, 0);
+                if options.formatted {
+                    renderer.out.push('\n');
+                }
+            }
+        }
+        renderer.out
+    }
+}
+
+struct Renderer<'a> {
+    options: &'a RenderOptions,
+    out: String,
+    path: Vec<String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn indent(&mut self, depth: usize) {
+        if self.options.formatted {
+            for _ in 0..depth {
+                self.out.push_str("    ");
+            }
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.options.formatted {
+            self.out.push('\n');
+        }
+    }
+
+    /// Write the comments describing a value: its origin and its error if it's a `BadValue`
+    fn comments(&mut self, value: &Hocon, depth: usize) {
+        if !self.options.comments() {
+            return;
+        }
+        let mut comments = vec![];
+        if let Some(origin) = self
+            .options
+            .origins
+            .as_ref()
+            .and_then(|origins| origins.get(&self.path))
+        {
+            comments.push(origin.to_string());
+        }
+        if let Hocon::BadValue(err) = value {
+            comments.push(err.to_string());
+        }
+        for comment in comments {
+            self.indent(depth);
+            let _ = writeln!(self.out, "# {}", comment);
+        }
+    }
+
+    fn render_key(&mut self, key: &str) {
+        if !self.options.json && self.options.unquoted_keys && can_be_unquoted_in_document(key) {
+            self.out.push_str(key);
+        } else {
+            write_quoted(&mut self.out, key);
+        }
+    }
+
+    /// Render the entries of an object, one per line, without the surrounding braces
+    fn render_entries(
+        &mut self,
+        hash: &linked_hash_map::LinkedHashMap<String, Hocon>,
+        depth: usize,
+    ) {
+        for (key, value) in hash {
+            self.path.push(key.clone());
+            self.comments(value, depth);
+            self.indent(depth);
+            self.render_key(key);
+            match value {
+                Hocon::Hash(_) => self.out.push(' '),
+                _ => self.out.push_str(" = "),
+            }
+            self.render_value(value, depth);
+            self.newline();
+            self.path.pop();
+        }
+    }
+
+    fn render_value(&mut self, value: &Hocon, depth: usize) {
+        match value {
+            Hocon::Real(f) if f.is_finite() => {
+                let _ = write!(self.out, "{:?}", f);
+            }
+            Hocon::Real(_) | Hocon::Null | Hocon::BadValue(_) => self.out.push_str("null"),
+            Hocon::Integer(i) => {
+                let _ = write!(self.out, "{}", i);
+            }
+            Hocon::String(s) => write_quoted(&mut self.out, s),
+            Hocon::Boolean(b) => {
+                let _ = write!(self.out, "{}", b);
+            }
+            Hocon::Array(vec) if vec.is_empty() => self.out.push_str("[]"),
+            Hocon::Array(vec) => {
+                self.out.push('[');
+                self.newline();
+                for (i, item) in vec.iter().enumerate() {
+                    self.path.push(i.to_string());
+                    self.comments(item, depth + 1);
+                    self.indent(depth + 1);
+                    self.render_value(item, depth + 1);
+                    self.path.pop();
+                    if i + 1 < vec.len() {
+                        self.out.push(',');
+                    }
+                    self.newline();
+                }
+                self.indent(depth);
+                self.out.push(']');
+            }
+            Hocon::Hash(hash) if hash.is_empty() => self.out.push_str("{}"),
+            Hocon::Hash(hash) if self.options.comments() => {
+                self.out.push_str("{\n");
+                self.render_entries(hash, depth + 1);
+                self.indent(depth);
+                self.out.push('}');
+            }
+            Hocon::Hash(hash) => {
+                self.out.push('{');
+                self.newline();
+                let separator = match (self.options.json, self.options.formatted) {
+                    (true, true) => ": ",
+                    (true, false) => ":",
+                    (false, true) => " = ",
+                    (false, false) => "=",
+                };
+                for (i, (key, item)) in hash.iter().enumerate() {
+                    self.indent(depth + 1);
+                    self.render_key(key);
+                    self.out.push_str(separator);
+                    self.path.push(key.clone());
+                    self.render_value(item, depth + 1);
+                    self.path.pop();
+                    if i + 1 < hash.len() {
+                        self.out.push(',');
+                    }
+                    self.newline();
+                }
+                self.indent(depth);
+                self.out.push('}');
+            }
+        }
+    }
+}
+
+/// Render a path as an HOCON path expression, quoting the keys that need it
+pub(crate) fn render_path<S: AsRef<str>>(path: &[S]) -> String {
+    let mut out = String::new();
+    for (i, key) in path.iter().enumerate() {
+        if i > 0 {
+            out.push('.');
+        }
+        if can_be_unquoted(key.as_ref()) {
+            out.push_str(key.as_ref());
+        } else {
+            write_quoted(&mut out, key.as_ref());
+        }
+    }
+    out
+}
+
+fn can_be_unquoted(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Keys of a document that would be read back as something else, like a keyword, a number or
+/// an include directive, are quoted. They don't need to be in a path expression
+fn can_be_unquoted_in_document(key: &str) -> bool {
+    can_be_unquoted(key)
+        && !key.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        && !["include", "true", "false", "null"].contains(&key)
+}
+
+fn write_quoted(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, HoconLoader};
+
+    #[test]
+    fn render_json() {
+        let doc = HoconLoader::new()
+            .load_str(r#"{ a: 1.0, "b.c": "quote\"d", d: [], e: {}, f: null, g: [true, 2] }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(
+            doc.render(&RenderOptions::new().json().concise()),
+            r#"{"a":1.0,"b.c":"quote\"d","d":[],"e":{},"f":null,"g":[true,2]}"#
+        );
+        assert_eq!(
+            doc.render(&RenderOptions::new().json()),
+            "{\n    \"a\": 1.0,\n    \"b.c\": \"quote\\\"d\",\n    \"d\": [],\n    \"e\": {},\n    \"f\": null,\n    \"g\": [\n        true,\n        2\n    ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn render_hocon() {
+        let doc = HoconLoader::new()
+            .load_str(r#"{ a: 1, "b.c": x, d { e: [{ f: 2 }] } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(
+            doc.render(&RenderOptions::new().concise()),
+            r#"{a=1,"b.c"="x",d={e=[{f=2}]}}"#
+        );
+        assert_eq!(
+            doc.render(&RenderOptions::new().concise().quoted_keys()),
+            r#"{"a"=1,"b.c"="x","d"={"e"=[{"f"=2}]}}"#
+        );
+        assert_eq!(
+            doc.render(&RenderOptions::new()),
+            "a = 1\n\"b.c\" = \"x\"\nd {\n    e = [\n        {\n            f = 2\n        }\n    ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn render_can_be_loaded_back() {
+        let doc = HoconLoader::new()
+            .load_file("tests/data/basic.conf")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        for options in &[
+            RenderOptions::new(),
+            RenderOptions::new().concise(),
+            RenderOptions::new().json(),
+            RenderOptions::new().json().concise(),
+        ] {
+            let rendered = dbg!(doc.render(options));
+            assert_eq!(
+                HoconLoader::new()
+                    .load_str(&rendered)
+                    .expect("during test")
+                    .hocon()
+                    .expect("during test"),
+                doc
+            );
+        }
+    }
+
+    #[test]
+    fn render_keys_read_back_differently_quoted() {
+        let doc = HoconLoader::new()
+            .load_str(
+                r#"{
+                    "include": 1, "true": 2, "false": 3, "null": 4, "1": 5, "-a": 6,
+                    "0x": { "includes": 7, "a-1": 8 }
+                }"#,
+            )
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(
+            doc.render(&RenderOptions::new().concise()),
+            r#"{"include"=1,"true"=2,"false"=3,"null"=4,"1"=5,"-a"=6,"0x"={includes=7,a-1=8}}"#
+        );
+        for options in &[RenderOptions::new(), RenderOptions::new().concise()] {
+            let rendered = dbg!(doc.render(options));
+            assert_eq!(
+                HoconLoader::new()
+                    .load_str(&rendered)
+                    .expect("during test")
+                    .hocon()
+                    .expect("during test"),
+                doc
+            );
+        }
+    }
+
+    #[test]
+    fn render_bad_value_and_origins() {
+        let (doc, origins) = HoconLoader::new()
+            .no_system()
+            .load_str("a = 1\nb = ${missing}\nc = [\n  2\n]")
+            .expect("during test")
+            .hocon_with_origins()
+            .expect("during test");
+        assert_eq!(
+            doc["b"],
+            Hocon::BadValue(Error::KeyNotFound {
+                key: String::from("missing")
+            })
+        );
+
+        assert_eq!(
+            doc.render(&RenderOptions::new().origin_comments(&origins)),
+            "# string:1\na = 1\n# string:2\n# Error looking for key '\"missing\"'\nb = null\nc = [\n    # string:4\n    2\n]\n"
+        );
+        assert_eq!(
+            doc.render(&RenderOptions::new().json().concise()),
+            r#"{"a":1,"b":null,"c":[2]}"#
+        );
+    }
+}
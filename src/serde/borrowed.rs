@@ -0,0 +1,483 @@
+//! Deserializer borrowing from an `Hocon` document, without cloning its values
+
+use std::convert::TryFrom;
+
+use crate::{Error, Hocon};
+
+use serde::de::{self, IntoDeserializer, Unexpected, Visitor};
+
+fn unexpected(value: &Hocon) -> Unexpected<'_> {
+    match value {
+        Hocon::Real(f) => Unexpected::Float(*f),
+        Hocon::Integer(i) => Unexpected::Signed(*i),
+        Hocon::String(s) => Unexpected::Str(s),
+        Hocon::Boolean(b) => Unexpected::Bool(*b),
+        Hocon::Array(_) => Unexpected::Seq,
+        Hocon::Hash(_) => Unexpected::Map,
+        Hocon::Null => Unexpected::Unit,
+        Hocon::BadValue(_) => Unexpected::Other("bad value"),
+    }
+}
+
+fn invalid_type(value: &Hocon, expected: &str) -> Error {
+    de::Error::invalid_type(unexpected(value), &expected)
+}
+
+macro_rules! impl_deserialize_n {
+    ($type:ty, $method:ident, $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            let value = self
+                .as_i64()
+                .or_else(|| self.as_bytes().and_then(|v| i64::try_from(v).ok()))
+                .ok_or_else(|| invalid_type(self, "integer"))?;
+            visitor.$visit(<$type>::try_from(value).map_err(|_| {
+                <Error as de::Error>::invalid_value(Unexpected::Signed(value), &stringify!($type))
+            })?)
+        }
+    };
+}
+
+macro_rules! impl_deserialize_f {
+    ($method:ident, $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.$visit(
+                self.as_f64()
+                    .or_else(|| self.as_bytes().map(|v| v as f64))
+                    .ok_or_else(|| invalid_type(self, "float"))?,
+            )
+        }
+    };
+    ($type:ty, $method:ident, $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.$visit(
+                self.as_f64()
+                    .or_else(|| self.as_bytes().map(|v| v as f64))
+                    .ok_or_else(|| invalid_type(self, "float"))? as $type,
+            )
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &'de Hocon {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::Boolean(b) => visitor.visit_bool(*b),
+            Hocon::Real(f) => visitor.visit_f64(*f),
+            Hocon::Integer(i) => visitor.visit_i64(*i),
+            Hocon::String(s) => visitor.visit_borrowed_str(s),
+            Hocon::Array(_) => self.deserialize_seq(visitor),
+            Hocon::Hash(_) => self.deserialize_map(visitor),
+            Hocon::Null => visitor.visit_unit(),
+            Hocon::BadValue(err) => Err(de::Error::custom(err)),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(self.as_bool().ok_or_else(|| invalid_type(self, "bool"))?)
+    }
+
+    impl_deserialize_n!(i8, deserialize_i8, visit_i8);
+    impl_deserialize_n!(i16, deserialize_i16, visit_i16);
+    impl_deserialize_n!(i32, deserialize_i32, visit_i32);
+    impl_deserialize_n!(i64, deserialize_i64, visit_i64);
+
+    impl_deserialize_n!(u8, deserialize_u8, visit_u8);
+    impl_deserialize_n!(u16, deserialize_u16, visit_u16);
+    impl_deserialize_n!(u32, deserialize_u32, visit_u32);
+    impl_deserialize_n!(u64, deserialize_u64, visit_u64);
+
+    impl_deserialize_f!(f32, deserialize_f32, visit_f32);
+    impl_deserialize_f!(deserialize_f64, visit_f64);
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut chars = match self {
+            Hocon::String(s) => s.chars(),
+            _ => return Err(invalid_type(self, "char")),
+        };
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(invalid_type(self, "char")),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::String(s) => visitor.visit_borrowed_str(s),
+            _ => visitor.visit_string(
+                self.as_string()
+                    .ok_or_else(|| invalid_type(self, "string"))?,
+            ),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::String(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            _ => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::Null | Hocon::BadValue(_) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::Array(vec) => visitor.visit_seq(de::value::SeqDeserializer::new(vec.iter())),
+            Hocon::Hash(hash) => {
+                // an object with numeric keys can be used as an array, ordered by its keys
+                let mut keys_as_usize = hash
+                    .iter()
+                    .filter_map(|(k, v)| k.parse::<usize>().ok().map(|k| (k, v)))
+                    .collect::<Vec<_>>();
+                keys_as_usize.sort_by_key(|(k, _)| *k);
+                visitor.visit_seq(de::value::SeqDeserializer::new(
+                    keys_as_usize.into_iter().map(|(_, v)| v),
+                ))
+            }
+            _ => Err(invalid_type(self, "sequence")),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            // a `BadValue` is treated as a missing key
+            Hocon::Hash(hash) => visitor.visit_map(de::value::MapDeserializer::new(
+                hash.iter()
+                    .filter(|(_, v)| !matches!(v, Hocon::BadValue(_)))
+                    .map(|(k, v)| (de::value::BorrowedStrDeserializer::new(k), v)),
+            )),
+            _ => Err(invalid_type(self, "map")),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Hocon::String(s) => visitor.visit_enum(s.as_str().into_deserializer()),
+            Hocon::Hash(hash) if hash.len() == 1 => {
+                let (variant, value) = hash.iter().next().expect("hash has one entry");
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            _ => Err(invalid_type(self, "enum")),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Hocon {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+struct EnumAccess<'de> {
+    variant: &'de str,
+    value: &'de Hocon,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(de::value::BorrowedStrDeserializer::<Error>::new(
+            self.variant,
+        ))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.value, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.value, visitor)
+    }
+}
+
+pub(crate) fn from_hocon_ref<'de, T>(hocon: &'de Hocon) -> Result<T, Error>
+where
+    T: de::Deserialize<'de>,
+{
+    serde_path_to_error::deserialize(hocon).map_err(|err| {
+        let path = err.path().to_string();
+        let message = match err.into_inner() {
+            Error::Deserialization { message } => message,
+            err => err.to_string(),
+        };
+        Error::Deserialization {
+            message: if path == "." {
+                message
+            } else {
+                format!("{}: {}", path, message)
+            },
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod tests {
+    use crate::{Error, Hocon, HoconLoader};
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Mode<'a> {
+        Off,
+        Fixed(u8),
+        Named { name: &'a str },
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Service<'a> {
+        host: &'a str,
+        port: u16,
+        timeout: f64,
+        enabled: bool,
+        tags: Vec<&'a str>,
+        labels: HashMap<&'a str, String>,
+        modes: Vec<Mode<'a>>,
+        missing: Option<u8>,
+        bad: Option<u8>,
+    }
+
+    fn doc() -> Hocon {
+        HoconLoader::new()
+            .no_system()
+            .load_str(
+                r#"{
+                    a: {
+                        host: localhost, port: "8080", timeout: 2, enabled: on
+                        tags: { "1": second, "0": first }
+                        labels: { team: core }
+                        modes: [Off, { Fixed: 3 }, { Named: { name: mine } }]
+                        bad: ${missing}
+                    }
+                    b: ${a} { host: example.com, port: 80 }
+                }"#,
+            )
+            .expect("during test")
+            .hocon()
+            .expect("during test")
+    }
+
+    #[test]
+    fn can_deserialize_borrowed_struct() {
+        let doc = doc();
+
+        let a: Service = dbg!(doc["a"].resolve_ref()).expect("during test");
+        let b: Service = dbg!(doc["b"].resolve_ref()).expect("during test");
+
+        assert_eq!(a.host, "localhost");
+        assert_eq!(a.port, 8080);
+        assert_eq!(a.timeout, 2.0);
+        assert!(a.enabled);
+        assert_eq!(a.tags, vec!["first", "second"]);
+        assert_eq!(a.labels["team"], "core");
+        assert_eq!(
+            a.modes,
+            vec![Mode::Off, Mode::Fixed(3), Mode::Named { name: "mine" }]
+        );
+        assert_eq!(a.missing, None);
+        assert_eq!(a.bad, None);
+        assert_eq!(b.host, "example.com");
+        assert_eq!(b.port, 80);
+    }
+
+    #[test]
+    fn can_deserialize_from_reference() {
+        let doc = doc();
+
+        let port = u16::deserialize(&doc["a"]["port"]).expect("during test");
+        let host = <&str>::deserialize(&doc["b"]["host"]).expect("during test");
+        let hocon = Hocon::deserialize(&doc["a"]["labels"]).expect("during test");
+
+        assert_eq!(port, 8080);
+        assert_eq!(host, "example.com");
+        assert_eq!(hocon, doc["a"]["labels"]);
+    }
+
+    #[test]
+    fn error_has_path() {
+        #[derive(Deserialize, Debug)]
+        struct Wrapper<'a> {
+            #[serde(borrow)]
+            b: Service<'a>,
+        }
+        #[derive(Deserialize, Debug)]
+        struct Small {
+            port: u8,
+        }
+
+        let doc = doc();
+
+        assert!(matches!(
+            dbg!(doc["a"].resolve_ref::<Small>()),
+            Err(Error::Deserialization { message }) if message.starts_with("port: ")
+        ));
+        assert!(matches!(
+            dbg!(doc["a"].resolve_ref::<Wrapper>()),
+            Err(Error::Deserialization { message }) if message.starts_with("missing field")
+        ));
+    }
+}
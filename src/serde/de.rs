@@ -129,7 +129,7 @@ impl std::fmt::Display for Index {
 
 trait Read {
     fn get_attribute_value(&self, index: &Index) -> Option<&Hocon>;
-    fn get_keys(&self) -> Vec<String>;
+    fn get_keys(&self) -> Result<Vec<String>>;
 }
 
 struct HoconRead {
@@ -151,14 +151,19 @@ impl Read for HoconRead {
                 Hocon::BadValue(_) => None,
                 v => Some(v),
             },
-            _ => None,
+            Index::None => match &self.hocon {
+                Hocon::BadValue(_) => None,
+                v => Some(v),
+            },
         }
     }
 
-    fn get_keys(&self) -> Vec<String> {
+    fn get_keys(&self) -> Result<Vec<String>> {
         match &self.hocon {
-            Hocon::Hash(map) => map.keys().cloned().collect(),
-            _ => unreachable!(),
+            Hocon::Hash(map) => Ok(map.keys().cloned().collect()),
+            _ => Err(Error {
+                message: String::from("invalid type for root, expected struct"),
+            }),
         }
     }
 }
@@ -431,7 +436,10 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
         V: serde::de::Visitor<'de>,
     {
         match self.current_field {
-            Index::None => visitor.visit_map(MapAccess::new(self, self.read.get_keys())),
+            Index::None => {
+                let keys = self.read.get_keys()?;
+                visitor.visit_map(MapAccess::new(self, keys))
+            }
             _ => {
                 let hc = self
                     .read
@@ -18,6 +18,13 @@ impl serde::de::Error for Error {
         }
     }
 }
+impl serde::de::Error for crate::Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> crate::Error {
+        crate::Error::Deserialization {
+            message: format!("{}", msg),
+        }
+    }
+}
 impl<E: std::fmt::Display> From<serde_path_to_error::Error<E>> for Error {
     fn from(msg: serde_path_to_error::Error<E>) -> Error {
         Error {
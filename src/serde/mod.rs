@@ -1,7 +1,12 @@
+mod borrowed;
 pub mod de;
+pub mod ser;
 
 pub mod wrappers;
 
 pub(crate) mod error;
 
+mod value;
+
+pub(crate) use borrowed::from_hocon_ref;
 pub(crate) use de::from_hocon;
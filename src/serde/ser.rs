@@ -0,0 +1,506 @@
+//! Serializer methods using serde
+
+use super::error::{Error, Result};
+use crate::Hocon;
+use linked_hash_map::LinkedHashMap;
+
+use serde::ser::{self, Serialize};
+
+/// Serializer building a [`Hocon`](../enum.Hocon.html) value
+struct Serializer;
+
+macro_rules! impl_serialize_n {
+    ($method:ident, $type:ty) => {
+        fn $method(self, v: $type) -> Result<Hocon> {
+            Ok(Hocon::Integer(i64::from(v)))
+        }
+    };
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Hocon;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Hocon> {
+        Ok(Hocon::Boolean(v))
+    }
+
+    impl_serialize_n!(serialize_i8, i8);
+    impl_serialize_n!(serialize_i16, i16);
+    impl_serialize_n!(serialize_i32, i32);
+    impl_serialize_n!(serialize_i64, i64);
+    impl_serialize_n!(serialize_u8, u8);
+    impl_serialize_n!(serialize_u16, u16);
+    impl_serialize_n!(serialize_u32, u32);
+
+    fn serialize_u64(self, v: u64) -> Result<Hocon> {
+        if v > i64::MAX as u64 {
+            return Err(Error {
+                message: format!("integer {} is too large for an HOCON integer", v),
+            });
+        }
+        Ok(Hocon::Integer(v as i64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Hocon> {
+        Ok(Hocon::Real(f64::from(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Hocon> {
+        Ok(Hocon::Real(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Hocon> {
+        Ok(Hocon::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Hocon> {
+        Ok(Hocon::String(String::from(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Hocon> {
+        Ok(Hocon::Array(
+            v.iter().map(|b| Hocon::Integer(i64::from(*b))).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Hocon> {
+        Ok(Hocon::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Hocon>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Hocon> {
+        Ok(Hocon::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Hocon> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Hocon> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Hocon>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Hocon>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut hash = LinkedHashMap::new();
+        hash.insert(String::from(variant), to_value(value)?);
+        Ok(Hocon::Hash(hash))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SerializeVec {
+            vec: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(SerializeTupleVariant {
+            name: String::from(variant),
+            vec: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeMap {
+            map: LinkedHashMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(SerializeStructVariant {
+            name: String::from(variant),
+            map: LinkedHashMap::new(),
+        })
+    }
+}
+
+struct SerializeVec {
+    vec: Vec<Hocon>,
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Hocon> {
+        Ok(Hocon::Array(self.vec))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Hocon> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Hocon> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant {
+    name: String,
+    vec: Vec<Hocon>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Hocon> {
+        let mut hash = LinkedHashMap::new();
+        hash.insert(self.name, Hocon::Array(self.vec));
+        Ok(Hocon::Hash(hash))
+    }
+}
+
+struct SerializeMap {
+    map: LinkedHashMap<String, Hocon>,
+    next_key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(key_to_string(to_value(key)?)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.next_key.take().ok_or_else(|| Error {
+            message: String::from("map value serialized before its key"),
+        })?;
+        self.map.insert(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Hocon> {
+        Ok(Hocon::Hash(self.map))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.map.insert(String::from(key), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Hocon> {
+        Ok(Hocon::Hash(self.map))
+    }
+}
+
+struct SerializeStructVariant {
+    name: String,
+    map: LinkedHashMap<String, Hocon>,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Hocon;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.map.insert(String::from(key), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Hocon> {
+        let mut hash = LinkedHashMap::new();
+        hash.insert(self.name, Hocon::Hash(self.map));
+        Ok(Hocon::Hash(hash))
+    }
+}
+
+fn key_to_string(key: Hocon) -> Result<String> {
+    match key {
+        Hocon::String(s) => Ok(s),
+        Hocon::Integer(i) => Ok(i.to_string()),
+        Hocon::Real(f) => Ok(f.to_string()),
+        Hocon::Boolean(b) => Ok(b.to_string()),
+        _ => Err(Error {
+            message: String::from("map keys must be strings, numbers or booleans"),
+        }),
+    }
+}
+
+fn to_value<T>(value: &T) -> Result<Hocon>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(Serializer)
+}
+
+/// Serialize a value to an [`Hocon`](../enum.Hocon.html) document
+///
+/// Structs and maps become [`Hocon::Hash`](../enum.Hocon.html#variant.Hash), sequences and
+/// tuples become [`Hocon::Array`](../enum.Hocon.html#variant.Array), and `None` or `()`
+/// become [`Hocon::Null`](../enum.Hocon.html#variant.Null). Enums are externally tagged,
+/// matching what [`de`](../de/index.html) expects.
+///
+/// # Usage
+///
+/// ```rust
+/// # use hocon::{Error, Hocon};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let doc = hocon::ser::to_hocon(&Server {
+///     host: String::from("localhost"),
+///     port: 8080,
+/// })?;
+///
+/// assert_eq!(doc["host"].as_string(), Some(String::from("localhost")));
+/// assert_eq!(doc["port"].as_i64(), Some(8080));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Error::Serialization`](../enum.Error.html#variant.Serialization) if the value can't
+///   be represented in HOCON (integer larger than `i64::MAX`, map key that isn't a string,
+///   ...)
+pub fn to_hocon<T>(value: &T) -> std::result::Result<Hocon, crate::Error>
+where
+    T: ?Sized + Serialize,
+{
+    to_value(value).map_err(|err| crate::Error::Serialization {
+        message: err.message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Hocon;
+    use linked_hash_map::LinkedHashMap;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Mode {
+        Off,
+        Fixed(u32),
+        Range(u32, u32),
+        Custom { name: String },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct WithEverything {
+        int: i64,
+        float: f64,
+        boolean: bool,
+        string: String,
+        option: Option<u8>,
+        vec: Vec<u16>,
+        modes: Vec<Mode>,
+    }
+
+    #[test]
+    fn can_serialize_struct() {
+        let value = WithEverything {
+            int: -5,
+            float: 1.5,
+            boolean: true,
+            string: String::from("test"),
+            option: None,
+            vec: vec![1, 2],
+            modes: vec![
+                Mode::Off,
+                Mode::Fixed(3),
+                Mode::Range(1, 2),
+                Mode::Custom {
+                    name: String::from("mine"),
+                },
+            ],
+        };
+
+        let doc = dbg!(super::to_hocon(&value)).expect("during test");
+
+        assert_eq!(doc["int"], Hocon::Integer(-5));
+        assert_eq!(doc["float"], Hocon::Real(1.5));
+        assert_eq!(doc["boolean"], Hocon::Boolean(true));
+        assert_eq!(doc["string"], Hocon::String(String::from("test")));
+        assert_eq!(doc["option"], Hocon::Null);
+        assert_eq!(
+            doc["vec"],
+            Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)])
+        );
+        assert_eq!(doc["modes"][0], Hocon::String(String::from("Off")));
+        assert_eq!(doc["modes"][1]["Fixed"], Hocon::Integer(3));
+        assert_eq!(doc["modes"][2]["Range"][1], Hocon::Integer(2));
+        assert_eq!(
+            doc["modes"][3]["Custom"]["name"],
+            Hocon::String(String::from("mine"))
+        );
+    }
+
+    #[test]
+    fn can_serialize_map_with_non_string_keys() {
+        let mut map = BTreeMap::new();
+        map.insert(1, "one");
+        map.insert(2, "two");
+
+        let mut expected = LinkedHashMap::new();
+        expected.insert(String::from("1"), Hocon::String(String::from("one")));
+        expected.insert(String::from("2"), Hocon::String(String::from("two")));
+
+        assert_eq!(
+            super::to_hocon(&map).expect("during test"),
+            Hocon::Hash(expected)
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert(vec![1], "one");
+        assert!(super::to_hocon(&map).is_err());
+    }
+
+    #[test]
+    fn can_not_serialize_large_integer() {
+        assert!(matches!(
+            super::to_hocon(&u64::MAX),
+            Err(crate::Error::Serialization { .. })
+        ));
+    }
+
+    #[test]
+    fn can_deserialize_serialized_struct() {
+        let value = WithEverything {
+            int: 12,
+            float: 0.25,
+            boolean: false,
+            string: String::from("round trip"),
+            option: Some(7),
+            vec: vec![],
+            modes: vec![Mode::Fixed(1), Mode::Off],
+        };
+
+        let doc = super::to_hocon(&value).expect("during test");
+        let back: WithEverything = dbg!(doc.resolve()).expect("during test");
+
+        assert_eq!(back, value);
+    }
+}
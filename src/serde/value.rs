@@ -0,0 +1,208 @@
+//! `Serialize` and `Deserialize` implementations for `Hocon`
+
+use crate::Hocon;
+use linked_hash_map::LinkedHashMap;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+impl Serialize for Hocon {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Hocon::Real(f) => serializer.serialize_f64(*f),
+            Hocon::Integer(i) => serializer.serialize_i64(*i),
+            Hocon::String(s) => serializer.serialize_str(s),
+            Hocon::Boolean(b) => serializer.serialize_bool(*b),
+            Hocon::Array(vec) => {
+                let mut seq = serializer.serialize_seq(Some(vec.len()))?;
+                for item in vec {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Hocon::Hash(hash) => {
+                let mut map = serializer.serialize_map(Some(hash.len()))?;
+                for (key, value) in hash {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Hocon::Null => serializer.serialize_unit(),
+            Hocon::BadValue(err) => Err(ser::Error::custom(err)),
+        }
+    }
+}
+
+struct HoconVisitor;
+
+impl<'de> Visitor<'de> for HoconVisitor {
+    type Value = Hocon;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any valid HOCON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Hocon, E> {
+        Ok(Hocon::Boolean(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Hocon, E> {
+        Ok(Hocon::Integer(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Hocon, E>
+    where
+        E: de::Error,
+    {
+        if v > i64::MAX as u64 {
+            Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(v),
+                &"an integer no larger than i64::MAX",
+            ))
+        } else {
+            Ok(Hocon::Integer(v as i64))
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Hocon, E> {
+        Ok(Hocon::Real(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Hocon, E> {
+        Ok(Hocon::String(String::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Hocon, E> {
+        Ok(Hocon::String(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Hocon, E> {
+        Ok(Hocon::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Hocon, E> {
+        Ok(Hocon::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Hocon, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Hocon, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            vec.push(item);
+        }
+        Ok(Hocon::Array(vec))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Hocon, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut hash = LinkedHashMap::new();
+        while let Some((key, value)) = map.next_entry::<String, Hocon>()? {
+            hash.insert(key, value);
+        }
+        Ok(Hocon::Hash(hash))
+    }
+}
+
+impl<'de> Deserialize<'de> for Hocon {
+    fn deserialize<D>(deserializer: D) -> Result<Hocon, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(HoconVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Hocon, HoconLoader};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize, Debug)]
+    struct WithPlugin {
+        name: String,
+        settings: Hocon,
+        extra: Option<Hocon>,
+    }
+
+    #[test]
+    fn can_deserialize_hocon_field() {
+        let doc = HoconLoader::new()
+            .load_str(r#"{ name: plugin, settings: { a: 1, b: [x, 2.5, true, null] } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let with_plugin: WithPlugin = dbg!(doc.clone().resolve()).expect("during test");
+
+        assert_eq!(with_plugin.settings, doc["settings"]);
+        assert_eq!(with_plugin.extra, None);
+    }
+
+    #[test]
+    fn can_resolve_to_hocon() {
+        let doc = HoconLoader::new()
+            .load_file("tests/data/basic.conf")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let resolved: Hocon = dbg!(doc.clone().resolve()).expect("during test");
+
+        assert_eq!(resolved, doc);
+    }
+
+    #[test]
+    fn can_resolve_scalar_and_array_roots_to_hocon() {
+        for root in &[
+            Hocon::Integer(5),
+            Hocon::Real(2.5),
+            Hocon::String(String::from("x")),
+            Hocon::Boolean(true),
+            Hocon::Null,
+            Hocon::Array(vec![Hocon::Integer(1), Hocon::String(String::from("y"))]),
+        ] {
+            let resolved: Hocon = dbg!(root.clone().resolve()).expect("during test");
+            assert_eq!(&resolved, root);
+        }
+
+        assert!(Hocon::BadValue(Error::MissingKey)
+            .resolve::<Hocon>()
+            .is_err());
+        assert!(Hocon::Integer(5).resolve::<WithPlugin>().is_err());
+    }
+
+    #[test]
+    fn can_convert_through_json() {
+        let doc = HoconLoader::new()
+            .load_str(r#"{ a: 1, b: { c: [x, 2.5, true, null] } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let json = serde_json::to_string(&doc).expect("during test");
+        assert_eq!(json, r#"{"a":1,"b":{"c":["x",2.5,true,null]}}"#);
+
+        let back: Hocon = serde_json::from_str(&json).expect("during test");
+        assert_eq!(back, doc);
+    }
+
+    #[test]
+    fn can_not_serialize_bad_value() {
+        assert!(serde_json::to_string(&Hocon::BadValue(Error::MissingKey)).is_err());
+        assert!(serde_json::from_str::<Hocon>("18446744073709551615").is_err());
+    }
+}
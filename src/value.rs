@@ -26,6 +26,13 @@ use std::ops::Index;
 /// Values can also be accessed as a `Duration` or a size following the rules described in
 /// [Units format](https://github.com/lightbend/config/blob/master/HOCON.md#units-format).
 ///
+/// Values can also be accessed by their path, as in `doc.get_i64("a.b.c")`. These getters
+/// return a `Result` whose error tells if the value is missing or of the wrong type, see
+/// [`get`](#method.get). Values can be changed by their path with [`set`](#method.set),
+/// [`remove`](#method.remove), [`get_mut`](#method.get_mut) and [`entry`](#method.entry),
+/// and whole documents can be layered with [`with_fallback`](#method.with_fallback) or
+/// flattened to their [`entries`](#method.entries).
+///
 /// # Usage
 ///
 /// ```rust
@@ -546,6 +553,567 @@ impl Hocon {
     }
 }
 
+impl Hocon {
+    /// Get the value at this path, as an HOCON path expression like `a.b."c.d"`
+    ///
+    /// Elements of an array can be reached with their index, as in `a.0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_str(r#"{ a { "b.c": { d: 7 }, e: [ first, second ] } }"#)?
+    ///     .hocon()?;
+    ///
+    /// assert_eq!(doc.get(r#"a."b.c".d"#)?, &Hocon::Integer(7));
+    /// assert_eq!(doc.get("a.e.1")?.as_string(), Some(String::from("second")));
+    /// assert_eq!(
+    ///     doc.get("a.b"),
+    ///     Err(Error::Missing { path: String::from("a.b") })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadPath`](enum.Error.html#variant.BadPath) if the path expression is invalid
+    /// * [`Error::Missing`](enum.Error.html#variant.Missing) if there is no value at this path,
+    ///   or if the value is `null`
+    /// * [`Error::WrongType`](enum.Error.html#variant.WrongType) if a value on the way is
+    ///   neither an object nor an array
+    /// * the error of the [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) at this path
+    pub fn get(&self, path: &str) -> Result<&Hocon, crate::Error> {
+        self.lookup(path).map(|(_, value)| value)
+    }
+
+    fn lookup(&self, path: &str) -> Result<(Vec<String>, &Hocon), crate::Error> {
+        let keys = crate::path::parse_path(path)?;
+        let missing = || Self::missing(&keys);
+
+        let mut value = self;
+        for (i, key) in keys.iter().enumerate() {
+            value = match value {
+                Hocon::Hash(hash) => hash.get(key),
+                Hocon::Array(vec) => key.parse::<usize>().ok().and_then(|idx| vec.get(idx)),
+                Hocon::Null | Hocon::BadValue(_) => None,
+                _ => return Err(value.wrong_type(&keys[..i], "object")),
+            }
+            .ok_or_else(missing)?;
+        }
+        match value {
+            Hocon::Null
+            | Hocon::BadValue(crate::Error::MissingKey)
+            | Hocon::BadValue(crate::Error::KeyNotFound { .. }) => Err(missing()),
+            Hocon::BadValue(err) => Err(err.clone()),
+            _ => Ok((keys, value)),
+        }
+    }
+
+    fn get_as<T>(
+        &self,
+        path: &str,
+        expected: &str,
+        cast: impl Fn(&Hocon) -> Option<T>,
+    ) -> Result<T, crate::Error> {
+        let (keys, value) = self.lookup(path)?;
+        cast(value).ok_or_else(|| value.wrong_type(&keys, expected))
+    }
+
+    fn wrong_type(&self, keys: &[String], expected: &str) -> crate::Error {
+        Self::wrong_type_of(self.type_name(), keys, expected)
+    }
+
+    fn wrong_type_of(found: &str, keys: &[String], expected: &str) -> crate::Error {
+        crate::Error::WrongType {
+            path: crate::render::render_path(keys),
+            expected: String::from(expected),
+            found: String::from(found),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Hocon::Real(_) | Hocon::Integer(_) => "number",
+            Hocon::String(_) => "string",
+            Hocon::Boolean(_) => "boolean",
+            Hocon::Array(_) => "list",
+            Hocon::Hash(_) => "object",
+            Hocon::Null => "null",
+            Hocon::BadValue(_) => "invalid value",
+        }
+    }
+
+    /// Get the value at this path as a `f64` value, see [`get`](#method.get) for the errors
+    pub fn get_f64(&self, path: &str) -> Result<f64, crate::Error> {
+        self.get_as(path, "number", Hocon::as_f64)
+    }
+
+    /// Get the value at this path as a `i64` value, see [`get`](#method.get) for the errors
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a { b { c: 7, d: seven } } }"#)?.hocon()?;
+    ///
+    /// assert_eq!(doc.get_i64("a.b.c")?, 7);
+    /// assert_eq!(
+    ///     doc.get_i64("a.b.d"),
+    ///     Err(Error::WrongType {
+    ///         path: String::from("a.b.d"),
+    ///         expected: String::from("integer"),
+    ///         found: String::from("string"),
+    ///     })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_i64(&self, path: &str) -> Result<i64, crate::Error> {
+        self.get_as(path, "integer", Hocon::as_i64)
+    }
+
+    /// Get the value at this path as a `String` value, see [`get`](#method.get) for the errors
+    pub fn get_string(&self, path: &str) -> Result<String, crate::Error> {
+        self.get_as(path, "string", Hocon::as_string)
+    }
+
+    /// Get the value at this path as a `bool` value, see [`get`](#method.get) for the errors
+    pub fn get_bool(&self, path: &str) -> Result<bool, crate::Error> {
+        self.get_as(path, "boolean", Hocon::as_bool)
+    }
+
+    /// Get the value at this path as a size in bytes, see [`as_bytes`](#method.as_bytes) for
+    /// the format and [`get`](#method.get) for the errors
+    pub fn get_bytes(&self, path: &str) -> Result<u64, crate::Error> {
+        self.get_as(path, "size in bytes", Hocon::as_bytes)
+    }
+
+    /// Get the value at this path as a `Duration`, see [`as_duration`](#method.as_duration) for
+    /// the format and [`get`](#method.get) for the errors
+    pub fn get_duration(&self, path: &str) -> Result<std::time::Duration, crate::Error> {
+        self.get_as(path, "duration", Hocon::as_duration)
+    }
+
+    /// Get the array at this path, see [`get`](#method.get) for the errors
+    pub fn get_array(&self, path: &str) -> Result<&[Hocon], crate::Error> {
+        let (keys, value) = self.lookup(path)?;
+        match value {
+            Hocon::Array(vec) => Ok(vec),
+            _ => Err(value.wrong_type(&keys, "list")),
+        }
+    }
+}
+
+impl Hocon {
+    /// Get a mutable reference to the value at this path, as an HOCON path expression like
+    /// `a.b."c.d"`
+    ///
+    /// Unlike [`get`](#method.get), `null` values and
+    /// [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) are returned.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadPath`](enum.Error.html#variant.BadPath) if the path expression is invalid
+    /// * [`Error::Missing`](enum.Error.html#variant.Missing) if there is no value at this path
+    /// * [`Error::WrongType`](enum.Error.html#variant.WrongType) if a value on the way is
+    ///   neither an object nor an array
+    pub fn get_mut(&mut self, path: &str) -> Result<&mut Hocon, crate::Error> {
+        let keys = crate::path::parse_path(path)?;
+        self.parent_mut(&keys, false)?
+            .slot(&keys, keys.len() - 1, false)?
+            .get()
+            .ok_or_else(|| Self::missing(&keys))
+    }
+
+    /// Set the value at this path, returning the value it replaced
+    ///
+    /// Missing objects on the way are created. A numeric key addresses an element of an
+    /// array, and the index just after the last element appends to the array. A value that
+    /// is replaced keeps its position in its object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let mut doc = HoconLoader::new().load_str(r#"{ a: 1, b: [ x ] }"#)?.hocon()?;
+    ///
+    /// assert_eq!(doc.set("a", Hocon::Integer(2))?, Some(Hocon::Integer(1)));
+    /// doc.set(r#"c."d.e".f"#, Hocon::Boolean(true))?;
+    /// doc.set("b.1", Hocon::String(String::from("y")))?;
+    ///
+    /// assert_eq!(doc.get_i64("a")?, 2);
+    /// assert_eq!(doc.get_bool(r#"c."d.e".f"#)?, true);
+    /// assert_eq!(doc.get_string("b.1")?, "y");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadPath`](enum.Error.html#variant.BadPath) if the path expression is invalid
+    /// * [`Error::Missing`](enum.Error.html#variant.Missing) if an index is past the end of
+    ///   an array
+    /// * [`Error::WrongType`](enum.Error.html#variant.WrongType) if a value on the way is
+    ///   neither an object, an array, nor `null`
+    pub fn set(&mut self, path: &str, value: Hocon) -> Result<Option<Hocon>, crate::Error> {
+        let keys = crate::path::parse_path(path)?;
+        Ok(self
+            .parent_mut(&keys, true)?
+            .slot(&keys, keys.len() - 1, true)?
+            .insert(value))
+    }
+
+    /// Remove the value at this path, returning it. Elements after a removed element of an
+    /// array are shifted
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadPath`](enum.Error.html#variant.BadPath) if the path expression is invalid
+    /// * [`Error::WrongType`](enum.Error.html#variant.WrongType) if a value on the way is
+    ///   neither an object, an array, nor `null`
+    pub fn remove(&mut self, path: &str) -> Result<Option<Hocon>, crate::Error> {
+        let keys = crate::path::parse_path(path)?;
+        match self
+            .parent_mut(&keys, false)
+            .and_then(|parent| parent.slot(&keys, keys.len() - 1, false))
+        {
+            Ok(slot) => Ok(slot.remove()),
+            Err(crate::Error::Missing { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get the entry at this path for in-place manipulation, creating the missing objects on
+    /// the way as [`set`](#method.set) does
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let mut doc = HoconLoader::new().load_str(r#"{ a { hits: 1 } }"#)?.hocon()?;
+    ///
+    /// for path in &["a.hits", "b.hits"] {
+    ///     doc.entry(path)?
+    ///         .and_modify(|hits| *hits = Hocon::Integer(hits.as_i64().unwrap_or(0) + 1))
+    ///         .or_insert(Hocon::Integer(1));
+    /// }
+    ///
+    /// assert_eq!(doc.get_i64("a.hits")?, 2);
+    /// assert_eq!(doc.get_i64("b.hits")?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set`](#method.set)
+    pub fn entry(&mut self, path: &str) -> Result<Entry<'_>, crate::Error> {
+        let keys = crate::path::parse_path(path)?;
+        let slot = self
+            .parent_mut(&keys, true)?
+            .slot(&keys, keys.len() - 1, true)?;
+        Ok(Entry { slot })
+    }
+
+    fn missing(keys: &[String]) -> crate::Error {
+        crate::Error::Missing {
+            path: crate::render::render_path(keys),
+        }
+    }
+
+    /// The object or array containing the value at this path
+    fn parent_mut(&mut self, keys: &[String], create: bool) -> Result<&mut Hocon, crate::Error> {
+        let mut value = self;
+        for i in 0..keys.len() - 1 {
+            let slot = value.slot(keys, i, create)?;
+            value = if create {
+                slot.or_insert_with(|| Hocon::Hash(LinkedHashMap::new()))
+            } else {
+                slot.get().ok_or_else(|| Self::missing(keys))?
+            };
+        }
+        Ok(value)
+    }
+
+    /// Where the value for the key `keys[i]` is in this object or array. If `create`, a
+    /// `null` value is first replaced by an empty object
+    fn slot(&mut self, keys: &[String], i: usize, create: bool) -> Result<Slot<'_>, crate::Error> {
+        if create {
+            if let Hocon::Null | Hocon::BadValue(_) = self {
+                *self = Hocon::Hash(LinkedHashMap::new());
+            }
+        }
+        let found = self.type_name();
+        match self {
+            Hocon::Hash(hash) => Ok(Slot::Key(hash, keys[i].clone())),
+            Hocon::Array(vec) => match keys[i].parse::<usize>() {
+                Ok(idx) if idx <= vec.len() => Ok(Slot::Index(vec, idx)),
+                Ok(_) => Err(Self::missing(&keys[..=i])),
+                Err(_) => Err(Self::wrong_type_of(found, &keys[..i], "object")),
+            },
+            Hocon::Null | Hocon::BadValue(_) => Err(Self::missing(keys)),
+            _ => Err(Self::wrong_type_of(found, &keys[..i], "object")),
+        }
+    }
+}
+
+/// A place in an HOCON document, that may be empty, as returned by
+/// [`Hocon::entry`](enum.Hocon.html#method.entry)
+#[derive(Debug)]
+pub struct Entry<'a> {
+    slot: Slot<'a>,
+}
+
+#[derive(Debug)]
+enum Slot<'a> {
+    Key(&'a mut LinkedHashMap<String, Hocon>, String),
+    // an index in the array, or its length to append to it
+    Index(&'a mut Vec<Hocon>, usize),
+}
+
+impl<'a> Slot<'a> {
+    fn get(self) -> Option<&'a mut Hocon> {
+        match self {
+            Slot::Key(hash, key) => hash.get_mut(&key),
+            Slot::Index(vec, idx) => vec.get_mut(idx),
+        }
+    }
+
+    fn or_insert_with<F: FnOnce() -> Hocon>(self, default: F) -> &'a mut Hocon {
+        match self {
+            Slot::Key(hash, key) => hash.entry(key).or_insert_with(default),
+            Slot::Index(vec, idx) => {
+                if idx == vec.len() {
+                    vec.push(default());
+                }
+                &mut vec[idx]
+            }
+        }
+    }
+
+    fn insert(self, value: Hocon) -> Option<Hocon> {
+        match self {
+            Slot::Key(hash, key) => match hash.get_mut(&key) {
+                Some(current) => Some(std::mem::replace(current, value)),
+                None => hash.insert(key, value),
+            },
+            Slot::Index(vec, idx) if idx == vec.len() => {
+                vec.push(value);
+                None
+            }
+            Slot::Index(vec, idx) => Some(std::mem::replace(&mut vec[idx], value)),
+        }
+    }
+
+    fn remove(self) -> Option<Hocon> {
+        match self {
+            Slot::Key(hash, key) => hash.remove(&key),
+            Slot::Index(vec, idx) if idx < vec.len() => Some(vec.remove(idx)),
+            Slot::Index(..) => None,
+        }
+    }
+}
+
+impl<'a> Entry<'a> {
+    /// Insert `default` if the entry is empty, and return a mutable reference to its value
+    pub fn or_insert(self, default: Hocon) -> &'a mut Hocon {
+        self.slot.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if the entry is empty, and return a mutable reference
+    /// to its value
+    pub fn or_insert_with<F: FnOnce() -> Hocon>(self, default: F) -> &'a mut Hocon {
+        self.slot.or_insert_with(default)
+    }
+
+    /// Modify the value of the entry, if it is not empty
+    pub fn and_modify<F: FnOnce(&mut Hocon)>(self, f: F) -> Self {
+        let slot = match self.slot {
+            Slot::Key(hash, key) => {
+                if let Some(value) = hash.get_mut(&key) {
+                    f(value);
+                }
+                Slot::Key(hash, key)
+            }
+            Slot::Index(vec, idx) => {
+                if let Some(value) = vec.get_mut(idx) {
+                    f(value);
+                }
+                Slot::Index(vec, idx)
+            }
+        };
+        Entry { slot }
+    }
+}
+
+impl Hocon {
+    /// Merge `other` into this value, `other` taking priority
+    ///
+    /// This follows the rules of
+    /// [duplicate keys and object merging](https://github.com/lightbend/config/blob/master/HOCON.md#duplicate-keys-and-object-merging),
+    /// as if `other` was loaded after this value: objects are merged recursively, and any
+    /// other value of `other`, including `null`, replaces the value of this document.
+    pub fn merge(&mut self, other: Hocon) {
+        match (self, other) {
+            (Hocon::Hash(hash), Hocon::Hash(other)) => {
+                for (key, value) in other {
+                    match hash.get_mut(&key) {
+                        Some(current) => current.merge(value),
+                        None => {
+                            hash.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (current, other) => *current = other,
+        }
+    }
+
+    /// Return this value merged with `fallback`, this value taking priority. See
+    /// [`merge`](#method.merge) for the rules
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let defaults = HoconLoader::new()
+    ///     .load_str(r#"{ server { host: localhost, port: 80 }, debug: true }"#)?
+    ///     .hocon()?;
+    /// let overrides = HoconLoader::new()
+    ///     .load_str(r#"{ debug: null, server { port: 8080 } }"#)?
+    ///     .hocon()?;
+    ///
+    /// let doc = overrides.with_fallback(&defaults);
+    /// assert_eq!(doc.get_string("server.host")?, "localhost");
+    /// assert_eq!(doc.get_i64("server.port")?, 8080);
+    /// assert_eq!(doc["debug"], Hocon::Null);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fallback(&self, fallback: &Hocon) -> Hocon {
+        let mut merged = fallback.clone();
+        merged.merge(self.clone());
+        merged
+    }
+}
+
+impl Hocon {
+    /// Flatten an object to the list of its values that are not objects, with their path as
+    /// an HOCON path expression like `a.b."c.d"`
+    ///
+    /// Arrays and `null` values are returned as is. Empty objects, and any value that is not
+    /// an object, have no entries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_str(r#"{ a { "b.c": 1, d: [ 2, 3 ] } }"#)?
+    ///     .hocon()?;
+    ///
+    /// let entries = doc.entries().collect::<Vec<_>>();
+    /// assert_eq!(entries[0], (String::from(r#"a."b.c""#), &Hocon::Integer(1)));
+    /// assert_eq!(entries[1].0, "a.d");
+    ///
+    /// let rebuilt = Hocon::from_entries(
+    ///     entries.into_iter().map(|(path, value)| (path, value.clone())),
+    /// )?;
+    /// assert_eq!(rebuilt, doc);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries(&self) -> impl Iterator<Item = (String, &Hocon)> {
+        fn walk<'a>(
+            value: &'a Hocon,
+            path: &mut Vec<String>,
+            found: &mut Vec<(String, &'a Hocon)>,
+        ) {
+            match value {
+                Hocon::Hash(hash) => {
+                    for (key, item) in hash {
+                        path.push(key.clone());
+                        walk(item, path, found);
+                        path.pop();
+                    }
+                }
+                _ if path.is_empty() => (),
+                _ => found.push((crate::render::render_path(path), value)),
+            }
+        }
+
+        let mut found = vec![];
+        walk(self, &mut vec![], &mut found);
+        found.into_iter()
+    }
+
+    /// Build an object from a list of values with their path, as returned by
+    /// [`entries`](#method.entries)
+    ///
+    /// Values are [`set`](#method.set) in order, so a path appearing twice keeps its last
+    /// value.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadPath`](enum.Error.html#variant.BadPath) if a path expression is invalid
+    /// * [`Error::WrongType`](enum.Error.html#variant.WrongType) if a path goes through a value
+    ///   set by a previous entry that is not an object
+    pub fn from_entries<I, S>(entries: I) -> Result<Hocon, crate::Error>
+    where
+        I: IntoIterator<Item = (S, Hocon)>,
+        S: AsRef<str>,
+    {
+        let mut doc = Hocon::Hash(LinkedHashMap::new());
+        for (path, value) in entries {
+            doc.set(path.as_ref(), value)?;
+        }
+        Ok(doc)
+    }
+}
+
+impl Hocon {
+    /// Errors of all the [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) of this document,
+    /// with their path
+    pub(crate) fn bad_values(&self) -> Vec<(Vec<String>, &crate::Error)> {
+        fn walk<'a>(
+            value: &'a Hocon,
+            path: &mut Vec<String>,
+            found: &mut Vec<(Vec<String>, &'a crate::Error)>,
+        ) {
+            match value {
+                Hocon::BadValue(err) => found.push((path.clone(), err)),
+                Hocon::Array(vec) => {
+                    for (i, item) in vec.iter().enumerate() {
+                        path.push(i.to_string());
+                        walk(item, path, found);
+                        path.pop();
+                    }
+                }
+                Hocon::Hash(hash) => {
+                    for (key, item) in hash {
+                        path.push(key.clone());
+                        walk(item, path, found);
+                        path.pop();
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut found = vec![];
+        walk(self, &mut vec![], &mut found);
+        found
+    }
+}
+
 impl Hocon {
     /// Deserialize the loaded documents to the target type
     ///
@@ -571,6 +1139,45 @@ impl Hocon {
             message: err.message,
         })
     }
+
+    /// Deserialize this document to the target type without consuming nor cloning it
+    ///
+    /// The target type can borrow from the document, for example with `&str` fields. This
+    /// is also available directly as `&Hocon` implements `serde::Deserializer`.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// struct Server<'a> {
+    ///     host: &'a str,
+    ///     port: u16,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_str(r#"{ front: { host: example.com, port: 80 }, back: { host: localhost, port: 8080 } }"#)?
+    ///     .hocon()?;
+    ///
+    /// let front: Server = doc["front"].resolve_ref()?;
+    /// let back: Server = doc["back"].resolve_ref()?;
+    /// assert_eq!(front.host, "example.com");
+    /// assert_eq!(back.port, 8080);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) if there was a
+    ///   serde error during deserialization (missing required field, type issue, ...)
+    #[cfg(feature = "serde-support")]
+    pub fn resolve_ref<'de, T>(&'de self) -> Result<T, crate::Error>
+    where
+        T: ::serde::Deserialize<'de>,
+    {
+        crate::serde::from_hocon_ref(self)
+    }
 }
 
 #[cfg(test)]
@@ -891,4 +1498,196 @@ mod tests {
             Some(std::time::Duration::from_secs(60 * 60 * 24 * 365))
         );
     }
+
+    #[test]
+    fn get_by_path() {
+        let doc = crate::HoconLoader::new()
+            .load_str(
+                r#"{ a { "b.c" { d: 7, e: 1.5 }, f: [ 10 MiB, 2 minutes ], g: null, h: yes } }"#,
+            )
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(doc.get_i64(r#"a."b.c".d"#), Ok(7));
+        assert_eq!(doc.get_f64(r#"a."b.c".e"#), Ok(1.5));
+        assert_eq!(doc.get_string(r#"a."b.c".d"#), Ok(String::from("7")));
+        assert_eq!(doc.get_bytes("a.f.0"), Ok(10 * 1024 * 1024));
+        assert_eq!(
+            doc.get_duration("a.f.1"),
+            Ok(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(doc.get_bool("a.h"), Ok(true));
+        assert_eq!(doc.get_array("a.f").map(<[Hocon]>::len), Ok(2));
+
+        let missing = |path: &str| {
+            Err(crate::Error::Missing {
+                path: String::from(path),
+            })
+        };
+        assert_eq!(doc.get_i64("a.b.c.d"), missing("a.b.c.d"));
+        assert_eq!(doc.get_i64("a.g"), missing("a.g"));
+        assert_eq!(doc.get_i64("a.g.x"), missing("a.g.x"));
+        assert_eq!(doc.get_i64("a.f.2"), missing("a.f.2"));
+
+        let wrong_type = |path: &str, expected: &str, found: &str| {
+            Err(crate::Error::WrongType {
+                path: String::from(path),
+                expected: String::from(expected),
+                found: String::from(found),
+            })
+        };
+        assert_eq!(
+            doc.get_i64(r#"a."b.c".e"#),
+            wrong_type(r#"a."b.c".e"#, "integer", "number")
+        );
+        assert_eq!(doc.get_i64("a.h.x"), wrong_type("a.h", "object", "string"));
+        assert_eq!(doc.get_i64("a"), wrong_type("a", "integer", "object"));
+        assert!(matches!(
+            doc.get_i64("a..d"),
+            Err(crate::Error::BadPath { .. })
+        ));
+    }
+
+    #[test]
+    fn edit_by_path() {
+        let mut doc = crate::HoconLoader::new()
+            .load_str(r#"{ a { b: 1, c: 2 }, d: [ x, { e: 3 } ], f: null, g: 4 }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(
+            doc.set("a.b", Hocon::Integer(10)),
+            Ok(Some(Hocon::Integer(1)))
+        );
+        assert!(matches!(&doc["a"], Hocon::Hash(hash) if hash.keys().eq(&["b", "c"])));
+        assert_eq!(doc.set(r#"h."i.j".k"#, Hocon::Null), Ok(None));
+        assert_eq!(doc[r#"h"#]["i.j"]["k"], Hocon::Null);
+        assert_eq!(doc.set("f.l", Hocon::Integer(5)), Ok(None));
+        assert_eq!(doc.get_i64("f.l"), Ok(5));
+        assert_eq!(doc.set("d.2", Hocon::Boolean(true)), Ok(None));
+        assert_eq!(
+            doc.set("d.1.e", Hocon::Integer(30)),
+            Ok(Some(Hocon::Integer(3)))
+        );
+        assert_eq!(
+            doc.set("d.4", Hocon::Null),
+            Err(crate::Error::Missing {
+                path: String::from("d.4")
+            })
+        );
+        assert!(matches!(
+            doc.set("g.m", Hocon::Null),
+            Err(crate::Error::WrongType { .. })
+        ));
+        assert!(matches!(
+            doc.set("d.n", Hocon::Null),
+            Err(crate::Error::WrongType { .. })
+        ));
+
+        *doc.get_mut("a.c").expect("during test") = Hocon::Integer(20);
+        assert_eq!(doc.get_i64("a.c"), Ok(20));
+        assert_eq!(
+            doc.get_mut("a.z").map(|_| ()),
+            Err(crate::Error::Missing {
+                path: String::from("a.z")
+            })
+        );
+
+        assert_eq!(
+            doc.remove("d.0"),
+            Ok(Some(Hocon::String(String::from("x"))))
+        );
+        assert_eq!(doc.get_i64("d.0.e"), Ok(30));
+        assert_eq!(doc.remove("a.z"), Ok(None));
+        assert_eq!(doc.remove("y.z"), Ok(None));
+        assert_eq!(
+            doc.remove("a"),
+            Ok(Some(Hocon::Hash({
+                let mut hash = LinkedHashMap::new();
+                hash.insert(String::from("b"), Hocon::Integer(10));
+                hash.insert(String::from("c"), Hocon::Integer(20));
+                hash
+            })))
+        );
+
+        *doc.entry("o.p")
+            .expect("during test")
+            .or_insert(Hocon::Integer(0)) = Hocon::Integer(1);
+        doc.entry("o.p")
+            .expect("during test")
+            .and_modify(|p| *p = Hocon::Integer(2))
+            .or_insert_with(|| unreachable!("entry is not empty"));
+        assert_eq!(doc.get_i64("o.p"), Ok(2));
+    }
+
+    #[test]
+    fn merge_values() {
+        let load = |s: &str| {
+            crate::HoconLoader::new()
+                .load_str(s)
+                .expect("during test")
+                .hocon()
+                .expect("during test")
+        };
+        let fallback = load(r#"{ a { b: 1, c: { d: 2 } }, e: [1, 2], f: 3, g: { h: 4 } }"#);
+        let priority = load(r#"{ a { c: { i: 5 }, j: 6 }, e: [3], f: null, g: 7, k: 8 }"#);
+
+        let merged = priority.with_fallback(&fallback);
+        assert_eq!(
+            merged,
+            load(r#"{ a { b: 1, c: { d: 2, i: 5 }, j: 6 }, e: [3], f: null, g: 7, k: 8 }"#)
+        );
+        assert!(matches!(&merged, Hocon::Hash(hash) if hash.keys().eq(&["a", "e", "f", "g", "k"])));
+
+        let mut doc = fallback;
+        doc.merge(priority);
+        assert_eq!(doc, merged);
+
+        let mut doc = load(r#"{ a: 1 }"#);
+        doc.merge(Hocon::Integer(2));
+        assert_eq!(doc, Hocon::Integer(2));
+    }
+
+    #[test]
+    fn entries_and_back() {
+        let doc = crate::HoconLoader::new()
+            .load_str(
+                r#"{ a { b: 1, "c.d": { "e f": x }, g: {} }, h: [1, { i: 2 }], j: null, "": 3 }"#,
+            )
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let entries = doc.entries().collect::<Vec<_>>();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a.b", r#"a."c.d"."e f""#, "h", "j", r#""""#]
+        );
+        assert_eq!(entries[1].1, &Hocon::String(String::from("x")));
+
+        let rebuilt = Hocon::from_entries(
+            entries
+                .into_iter()
+                .map(|(path, value)| (path, value.clone())),
+        )
+        .expect("during test");
+        let mut expected = doc.clone();
+        expected.remove("a.g").expect("during test");
+        assert_eq!(rebuilt, expected);
+
+        assert_eq!(Hocon::Integer(1).entries().count(), 0);
+        assert_eq!(
+            Hocon::from_entries(vec![("a", Hocon::Integer(1)), ("a", Hocon::Integer(2))]),
+            Ok(Hocon::from_entries(vec![("a", Hocon::Integer(2))]).expect("during test"))
+        );
+        assert!(matches!(
+            Hocon::from_entries(vec![("a", Hocon::Integer(1)), ("a.b", Hocon::Integer(2))]),
+            Err(crate::Error::WrongType { .. })
+        ));
+    }
 }
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::{Dependency, Error, Hocon, HoconLoader};
+
+/// Watch the files read while loading documents, and reload them when they change
+///
+/// The documents are loaded by a function returning a [`HoconLoader`](struct.HoconLoader.html),
+/// so that the whole chain of `load_*` calls is replayed on every reload. Every file read
+/// during the load is polled, including the ones read by `include` directives and the ones
+/// that were looked for but not found. When one changes, the documents are loaded again and
+/// the callback is called with the new `Hocon` document, or with the error that happened.
+/// Changes are detected from the modification time and the size of the files, and from the
+/// list of files of the directories loaded with
+/// [`load_dir`](struct.HoconLoader.html#method.load_dir) or by glob includes.
+///
+/// Polling stops when the `ConfigWatcher` is dropped.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// # use hocon::{ConfigWatcher, HoconLoader, Error};
+/// # use std::time::Duration;
+/// # fn main() -> Result<(), Error> {
+/// let (doc, watcher) = ConfigWatcher::watch(
+///     || HoconLoader::new().load_file("application.conf"),
+///     Duration::from_secs(5),
+///     |reloaded| match reloaded {
+///         Ok(doc) => println!("new configuration: {:?}", doc),
+///         Err(error) => eprintln!("invalid configuration: {}", error),
+///     },
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    stop: Option<mpsc::Sender<()>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+/// Modification time and size of a file, with the entries of a directory, `None` if it
+/// doesn't exist
+type FileState = Option<(Option<SystemTime>, u64, Vec<PathBuf>)>;
+
+impl ConfigWatcher {
+    /// Load the documents, then poll the files read every `interval`, calling `on_change`
+    /// after they are reloaded
+    ///
+    /// # Errors
+    ///
+    /// * the error of the first load, in which case nothing is watched
+    pub fn watch<L, C>(load: L, interval: Duration, on_change: C) -> Result<(Hocon, Self), Error>
+    where
+        L: Fn() -> Result<HoconLoader, Error> + Send + 'static,
+        C: FnMut(Result<Hocon, Error>) + Send + 'static,
+    {
+        let (doc, files) = Self::load(&load)?;
+        let mut on_change = on_change;
+        let mut states = Self::states(files);
+        let (stop, stopped) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let current = Self::states(states.keys().cloned());
+                if current == states {
+                    continue;
+                }
+                match Self::load(&load) {
+                    Ok((doc, files)) => {
+                        states = Self::states(files);
+                        on_change(Ok(doc));
+                    }
+                    Err(error) => {
+                        states = current;
+                        on_change(Err(error));
+                    }
+                }
+            }
+        });
+        Ok((
+            doc,
+            Self {
+                stop: Some(stop),
+                handle: Some(handle),
+            },
+        ))
+    }
+
+    fn load<L>(load: &L) -> Result<(Hocon, Vec<PathBuf>), Error>
+    where
+        L: Fn() -> Result<HoconLoader, Error>,
+    {
+        let loader = load()?;
+        let files = loader
+            .dependencies()
+            .iter()
+            .filter_map(Dependency::path)
+            .map(PathBuf::from)
+            .collect();
+        Ok((loader.hocon()?, files))
+    }
+
+    fn states(files: impl IntoIterator<Item = PathBuf>) -> BTreeMap<PathBuf, FileState> {
+        files
+            .into_iter()
+            .map(|file| {
+                let state = std::fs::metadata(&file).ok().map(|metadata| {
+                    let entries = if metadata.is_dir() {
+                        Self::entries(&file)
+                    } else {
+                        vec![]
+                    };
+                    (metadata.modified().ok(), metadata.len(), entries)
+                });
+                (file, state)
+            })
+            .collect()
+    }
+
+    fn entries(dir: &Path) -> Vec<PathBuf> {
+        let mut entries = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        entries.sort();
+        entries
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
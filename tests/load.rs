@@ -24,3 +24,806 @@ fn missing_file() {
 
     assert!(dbg!(doc).is_err());
 }
+
+#[test]
+fn origins_follow_includes() {
+    let (_, origins) = hocon::HoconLoader::new()
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    let a = dbg!(origins.get(&["a"])).expect("during test");
+    assert!(a.path().expect("during test").ends_with("include.conf"));
+    assert_eq!(a.line(), Some(8));
+    assert_eq!(a.included_from(), None);
+
+    let element = dbg!(origins.get(&["c", "1"])).expect("during test");
+    assert!(element.path().expect("during test").ends_with("basic.conf"));
+    assert_eq!(element.line(), Some(4));
+    assert_eq!(
+        element.included_from().and_then(hocon::Origin::line),
+        Some(1)
+    );
+}
+
+#[test]
+fn origins_of_string_and_properties() {
+    let (_, origins) = hocon::HoconLoader::new()
+        .load_str("a = 1\nb {\n  c = [1, 2]\n}\nb.d = 3")
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    assert_eq!(origins.get(&["a"]).and_then(hocon::Origin::line), Some(1));
+    assert_eq!(
+        origins.get(&["b", "c", "1"]).and_then(hocon::Origin::line),
+        Some(3)
+    );
+    assert_eq!(
+        origins.get(&["b", "d"]).and_then(hocon::Origin::line),
+        Some(5)
+    );
+    assert_eq!(origins.get(&["b", "d"]).and_then(hocon::Origin::path), None);
+
+    let (_, origins) = hocon::HoconLoader::new()
+        .load_file("tests/data/file_with_different_types.properties")
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+    let origin = origins.iter().next().expect("during test").1;
+    assert!(origin
+        .path()
+        .expect("during test")
+        .ends_with("file_with_different_types.properties"));
+    assert_eq!(origin.line(), None);
+}
+
+#[test]
+fn collect_all_errors() {
+    let doc = hocon::HoconLoader::new()
+        .no_system()
+        .collect_errors()
+        .load_file("tests/data/missing.conf")
+        .expect("during test")
+        .load_str("a = ${missing}\nb { c = [1, ${other}] }\nd = {")
+        .expect("during test")
+        .load_str("e = ${missing}\ninclude \"no_file.conf\"")
+        .expect("during test")
+        .hocon();
+
+    let errors = match dbg!(doc) {
+        Err(hocon::Error::Validation { errors }) => errors,
+        _ => panic!("document should be invalid"),
+    };
+    assert_eq!(
+        errors.iter().map(|error| error.path()).collect::<Vec<_>>(),
+        vec!["", "", "", "e"]
+    );
+    assert!(matches!(errors[0].error(), hocon::Error::File { .. }));
+    assert!(matches!(
+        errors[1].error(),
+        hocon::Error::Parse { line: 3, .. }
+    ));
+    assert_eq!(errors[2].error(), &hocon::Error::IncludeNotAllowedFromStr);
+    assert_eq!(
+        errors[3].error(),
+        &hocon::Error::KeyNotFound {
+            key: String::from("missing")
+        }
+    );
+}
+
+#[test]
+fn collect_errors_of_valid_document() {
+    let doc = hocon::HoconLoader::new()
+        .collect_errors()
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .hocon();
+
+    assert!(dbg!(doc).is_ok());
+}
+
+#[test]
+fn lenient_diagnostics() {
+    let (doc, diagnostics) = hocon::HoconLoader::new()
+        .no_system()
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .load_str("f.h = ${missing}\ng = [\n  1,\n  ${other}\n]\ninclude \"no_file.conf\"")
+        .expect("during test")
+        .hocon_with_diagnostics()
+        .expect("during test");
+
+    assert_eq!(doc["a"], hocon::Hocon::Integer(5));
+    assert_eq!(
+        dbg!(&diagnostics)
+            .iter()
+            .map(|diagnostic| (
+                diagnostic.path(),
+                diagnostic.origin().and_then(hocon::Origin::line)
+            ))
+            .collect::<Vec<_>>(),
+        vec![("", Some(6)), ("f.h", Some(1)), ("g.1", Some(4))]
+    );
+    assert_eq!(
+        diagnostics[0].error(),
+        &hocon::Error::IncludeNotAllowedFromStr
+    );
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "f.h: Error looking for key '\"missing\"' (string:1)"
+    );
+    assert!(matches!(&doc, hocon::Hocon::Hash(hash) if !hash.contains_key("no_file.conf")));
+}
+
+#[test]
+fn load_from_reader() {
+    let file = File::open("tests/data/include.conf").expect("during test");
+    let (doc, origins) = hocon::HoconLoader::new()
+        .strict()
+        .load_reader(
+            file,
+            hocon::Format::Hocon,
+            Some(std::path::Path::new("tests/data/virtual.conf")),
+        )
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    assert_eq!(doc["b"].as_f64(), Some(6.7));
+    assert!(origins
+        .get(&["a"])
+        .and_then(hocon::Origin::path)
+        .expect("during test")
+        .ends_with("virtual.conf"));
+
+    let file = File::open("tests/data/include.conf").expect("during test");
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .load_reader(file, hocon::Format::Hocon, None);
+    assert_eq!(
+        dbg!(doc).map(|_| ()),
+        Err(hocon::Error::IncludeNotAllowedFromStr)
+    );
+}
+
+#[test]
+fn include_from_resolver() {
+    let mut documents = std::collections::HashMap::new();
+    documents.insert(
+        String::from("defaults.conf"),
+        String::from("a = 1\ninclude classpath(\"nested.json\")"),
+    );
+    documents.insert(String::from("nested.json"), String::from(r#"{"b": 2}"#));
+
+    let (doc, origins) = hocon::HoconLoader::new()
+        .strict()
+        .include_resolver(documents)
+        .load_str("include \"defaults.conf\"\nc { include file(\"nested.json\") }")
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    assert_eq!(doc["a"].as_i64(), Some(1));
+    assert_eq!(doc["b"].as_i64(), Some(2));
+    assert_eq!(doc["c"]["b"].as_i64(), Some(2));
+    assert_eq!(
+        origins.get(&["b"]).map(ToString::to_string),
+        Some(String::from(
+            "nested.json:1 via defaults.conf:2 via string:1"
+        ))
+    );
+}
+
+#[test]
+fn resolver_falls_back_to_files() {
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .include_resolver(std::collections::HashMap::<String, String>::new())
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["b"].as_f64(), Some(6.7));
+
+    let (doc, diagnostics) = hocon::HoconLoader::new()
+        .load_str("a { include file(\"missing.conf\") }")
+        .expect("during test")
+        .hocon_with_diagnostics()
+        .expect("during test");
+
+    assert!(matches!(&doc["a"], hocon::Hocon::Hash(hash) if hash.is_empty()));
+    assert_eq!(
+        diagnostics[0].error(),
+        &hocon::Error::IncludeNotAllowedFromStr
+    );
+}
+
+#[test]
+fn optional_and_required_includes() {
+    let load = |loader: hocon::HoconLoader, content: &str| {
+        loader.load_reader(
+            content.as_bytes(),
+            hocon::Format::Hocon,
+            Some(std::path::Path::new("tests/data/virtual.conf")),
+        )
+    };
+
+    let doc = load(
+        hocon::HoconLoader::new().strict(),
+        r#"include "missing.conf"
+include file("missing.conf")
+include classpath("missing.conf")
+x = 1"#,
+    )
+    .expect("during test")
+    .hocon()
+    .expect("during test");
+    assert_eq!(dbg!(&doc)["x"].as_i64(), Some(1));
+    assert!(matches!(&doc, hocon::Hocon::Hash(hash) if hash.len() == 1));
+
+    let doc = load(
+        hocon::HoconLoader::new().strict(),
+        r#"include required("basic.conf")
+sub { include required( file("substitution.conf") ) }"#,
+    )
+    .expect("during test")
+    .hocon()
+    .expect("during test");
+    assert_eq!(doc["b"].as_f64(), Some(6.7));
+    assert!(matches!(dbg!(&doc["sub"]), hocon::Hocon::Hash(_)));
+
+    let doc = load(
+        hocon::HoconLoader::new().strict(),
+        r#"include required("missing.conf")"#,
+    );
+    assert_eq!(
+        dbg!(doc).map(|_| ()),
+        Err(hocon::Error::Include {
+            path: String::from("missing.conf")
+        })
+    );
+
+    let (doc, diagnostics) = load(
+        hocon::HoconLoader::new(),
+        r#"a { include required(file("missing.conf")) }"#,
+    )
+    .expect("during test")
+    .hocon_with_diagnostics()
+    .expect("during test");
+    assert!(matches!(dbg!(&doc["a"]), hocon::Hocon::Hash(hash) if hash.is_empty()));
+    assert_eq!(
+        dbg!(&diagnostics)
+            .iter()
+            .map(|d| (
+                d.path(),
+                d.error(),
+                d.origin().and_then(hocon::Origin::line)
+            ))
+            .collect::<Vec<_>>(),
+        vec![(
+            "",
+            &hocon::Error::Include {
+                path: String::from("missing.conf")
+            },
+            Some(1)
+        )]
+    );
+}
+
+#[test]
+fn unreadable_includes() {
+    let dir = std::env::temp_dir().join(format!("hocon-unreadable-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("dir.conf")).expect("during test");
+    std::fs::write(dir.join("bad.conf"), b"a = \xff\xfe\n").expect("during test");
+    let load = |loader: hocon::HoconLoader, content: &str| {
+        loader
+            .load_reader(
+                content.as_bytes(),
+                hocon::Format::Hocon,
+                Some(&dir.join("main.conf")),
+            )
+            .and_then(hocon::HoconLoader::hocon)
+    };
+    let file_error = |name: &str| {
+        Err(hocon::Error::File {
+            path: dir.join(name).display().to_string(),
+        })
+    };
+
+    for content in &[
+        "include \"bad.conf\"\nz = 1\n",
+        "include required(\"bad.conf\")\nz = 1\n",
+        "include file(\"bad.conf\")\nz = 1\n",
+    ] {
+        assert_eq!(
+            dbg!(load(hocon::HoconLoader::new().strict(), content)),
+            file_error("bad.conf")
+        );
+    }
+    assert_eq!(
+        load(hocon::HoconLoader::new().strict(), "include \"dir.conf\"\n"),
+        file_error("dir.conf")
+    );
+    assert_eq!(
+        load(
+            hocon::HoconLoader::new().strict(),
+            &format!(
+                "include url(\"file://{}\")\n",
+                dir.join("bad.conf").display()
+            )
+        ),
+        file_error("bad.conf")
+    );
+
+    let doc =
+        load(hocon::HoconLoader::new(), "include \"bad.conf\"\nz = 1\n").expect("during test");
+    assert_eq!(dbg!(&doc)["z"].as_i64(), Some(1));
+    assert!(matches!(&doc, hocon::Hocon::Hash(hash) if hash.len() == 1));
+
+    let (_, diagnostics) = hocon::HoconLoader::new()
+        .load_reader(
+            "include \"bad.conf\"\nz = 1\n".as_bytes(),
+            hocon::Format::Hocon,
+            Some(&dir.join("main.conf")),
+        )
+        .expect("during test")
+        .hocon_with_diagnostics()
+        .expect("during test");
+    assert_eq!(
+        dbg!(diagnostics)
+            .iter()
+            .map(|d| (d.path(), d.error().clone()))
+            .collect::<Vec<_>>(),
+        vec![("", file_error("bad.conf").unwrap_err())]
+    );
+
+    std::fs::remove_dir_all(&dir).expect("during test");
+}
+
+#[test]
+fn classpath_includes() {
+    let (doc, origins) = hocon::HoconLoader::new()
+        .strict()
+        .classpath_resource("reference.conf", "a = 1, b = 2")
+        .classpath_resource("reference.json", r#"{ "b": 3, "c": 4 }"#)
+        .classpath_resource("lib/defaults.properties", "d=5")
+        .classpath_dir("tests/data")
+        .load_str(
+            r#"{
+                include classpath("reference")
+                include classpath("/lib/defaults.properties")
+                basic { include required(classpath("basic.conf")) }
+                include classpath("missing.conf")
+            }"#,
+        )
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    assert_eq!(dbg!(&doc)["a"].as_i64(), Some(1));
+    assert_eq!(doc["b"].as_i64(), Some(2));
+    assert_eq!(doc["c"].as_i64(), Some(4));
+    assert_eq!(doc["d"].as_i64(), Some(5));
+    assert_eq!(doc["basic"]["b"].as_f64(), Some(6.7));
+    assert_eq!(
+        origins.get(&["a"]).and_then(hocon::Origin::path),
+        Some("classpath:reference.conf")
+    );
+    assert!(origins
+        .get(&["basic", "b"])
+        .and_then(hocon::Origin::path)
+        .expect("during test")
+        .ends_with("basic.conf"));
+
+    let doc = hocon::HoconLoader::new()
+        .classpath_resource("basic.conf", "b = embedded")
+        .classpath_dir("tests/data")
+        .load_str(r#"include classpath("basic.conf")"#)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["b"].as_string(), Some(String::from("embedded")));
+
+    let (doc, origins) = hocon::HoconLoader::new()
+        .strict()
+        .classpath_resource("app/main.conf", "include \"lib/nested.conf\"\nmain = 1\n")
+        .classpath_resource("app/lib/nested.conf", "include \"/top\"\nnested = 2\n")
+        .classpath_resource("top.conf", "top = 3")
+        .load_str(r#"include required(classpath("app/main.conf"))"#)
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+    assert_eq!(dbg!(&doc)["main"].as_i64(), Some(1));
+    assert_eq!(doc["nested"].as_i64(), Some(2));
+    assert_eq!(doc["top"].as_i64(), Some(3));
+    assert_eq!(
+        origins.get(&["nested"]).and_then(hocon::Origin::path),
+        Some("classpath:app/lib/nested.conf")
+    );
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .load_str(r#"include required(classpath("missing.conf"))"#);
+    assert_eq!(
+        dbg!(doc).map(|_| ()),
+        Err(hocon::Error::Include {
+            path: String::from("missing.conf")
+        })
+    );
+}
+
+#[test]
+fn include_paths() {
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .include_path("tests/data/missing_dir")
+        .include_path("tests")
+        .include_path("tests/data")
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .load_str(r#"from_str { include required("substitution.conf") }"#)
+        .expect("during test")
+        .load_str(r#"shadowed { include required(file("data/basic.conf")) }"#)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(dbg!(&doc)["b"].as_f64(), Some(6.7));
+    assert!(matches!(doc["from_str"], hocon::Hocon::Hash(_)));
+    assert_eq!(doc["shadowed"]["b"].as_f64(), Some(6.7));
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .include_path("tests")
+        .load_str(r#"include required("basic.conf")"#);
+    assert_eq!(
+        dbg!(doc).map(|_| ()),
+        Err(hocon::Error::Include {
+            path: String::from("basic.conf")
+        })
+    );
+}
+
+#[test]
+fn glob_includes_and_load_dir() {
+    let from_dir = hocon::HoconLoader::new()
+        .strict()
+        .load_dir("tests/data/conf.d")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(dbg!(&from_dir)["app"]["port"].as_i64(), Some(9090));
+    assert_eq!(
+        from_dir["app"]["name"].as_string(),
+        Some(String::from("override"))
+    );
+    assert_eq!(
+        from_dir["app"]["tags"][1],
+        hocon::Hocon::BadValue(hocon::Error::MissingKey)
+    );
+
+    let from_glob = hocon::HoconLoader::new()
+        .strict()
+        .load_reader(
+            r#"include required("conf.d/*")"#.as_bytes(),
+            hocon::Format::Hocon,
+            Some(std::path::Path::new("tests/data/virtual.conf")),
+        )
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(from_glob, from_dir);
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .include_path("tests/data")
+        .load_str(
+            r#"{
+                include "conf.d/*.conf"
+                include "conf.d/*.missing"
+                include "conf.d/.hidden*"
+                json { include required(file("conf.d/??-*.json")) }
+            }"#,
+        )
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(dbg!(&doc)["app"]["port"].as_i64(), Some(8080));
+    assert_eq!(doc["app"]["tags"][1].as_string(), Some(String::from("b")));
+    assert_eq!(doc["json"]["app"]["port"].as_i64(), Some(9090));
+
+    assert_eq!(
+        hocon::HoconLoader::new()
+            .load_dir("tests/data/missing_dir")
+            .map(|_| ()),
+        Err(hocon::Error::File {
+            path: std::env::current_dir()
+                .expect("during test")
+                .join("tests/data/missing_dir")
+                .display()
+                .to_string()
+        })
+    );
+}
+
+#[test]
+fn env_overrides() {
+    let env = vec![
+        ("HOCON_TEST_OVERRIDE_b", "overridden"),
+        ("HOCON_TEST_OVERRIDE_f_g", "from env"),
+        ("HOCON_TEST_OVERRIDE_new__key", "new"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (String::from(name), String::from(value)))
+    .collect::<std::collections::HashMap<_, _>>();
+
+    let (doc, origins) = hocon::HoconLoader::new()
+        .with_env(env)
+        .env_overrides("HOCON_TEST_OVERRIDE_")
+        .load_file("tests/data/basic.conf")
+        .expect("during test")
+        .load_str(r#"{ b = 1, f.h = 2 }"#)
+        .expect("during test")
+        .hocon_with_origins()
+        .expect("during test");
+
+    assert_eq!(
+        dbg!(&doc)["b"].as_string(),
+        Some(String::from("overridden"))
+    );
+    assert_eq!(doc["f"]["g"].as_string(), Some(String::from("from env")));
+    assert_eq!(doc["f"]["h"].as_i64(), Some(2));
+    assert_eq!(doc["new-key"].as_string(), Some(String::from("new")));
+    assert_eq!(doc["a"].as_i64(), Some(5));
+    assert_eq!(
+        origins.get(&["f", "g"]).and_then(hocon::Origin::path),
+        Some("env:HOCON_TEST_OVERRIDE_f_g")
+    );
+
+    let doc = hocon::HoconLoader::new()
+        .load_str(r#"{ b = 1 }"#)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["b"].as_i64(), Some(1));
+}
+
+#[test]
+fn substitutions_from_env_source() {
+    let mut env = std::collections::HashMap::new();
+    env.insert(String::from("TENANT"), String::from("acme"));
+    env.insert(String::from("a.b"), String::from("dotted"));
+
+    let doc = hocon::HoconLoader::new()
+        .with_env(env)
+        .load_str(r#"{ name = ${TENANT}, dotted = ${a.b}, path = ${?PATH}, home = ${HOME} }"#)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(dbg!(&doc)["name"].as_string(), Some(String::from("acme")));
+    assert_eq!(doc["dotted"].as_string(), Some(String::from("dotted")));
+    assert_eq!(doc["path"].as_string(), None);
+    assert_eq!(
+        doc["home"],
+        hocon::Hocon::BadValue(hocon::Error::KeyNotFound {
+            key: String::from("HOME")
+        })
+    );
+}
+
+#[test]
+fn load_default_chain() {
+    let env = |vars: &[(&str, &str)]| {
+        vars.iter()
+            .map(|(name, value)| (String::from(*name), String::from(*value)))
+            .collect::<std::collections::HashMap<_, _>>()
+    };
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .with_env(env(&[
+            ("TEST_APP_2_CONFIG_FILE", "tests/data/basic.conf"),
+            ("CONFIG_FORCE_app_port", "9090"),
+        ]))
+        .classpath_dir("tests/data/app")
+        .load_default("test-app.2", None)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        dbg!(&doc)["app"]["name"].as_string(),
+        Some(String::from("application"))
+    );
+    assert_eq!(
+        doc["app"]["url"].as_string(),
+        Some(String::from("http://example.com:8080"))
+    );
+    assert_eq!(doc["app"]["port"].as_i64(), Some(9090));
+    assert_eq!(doc["b"].as_f64(), Some(6.7));
+
+    let doc = hocon::HoconLoader::new()
+        .with_env(env(&[("CONFIG_FORCE_app_port", "1"), ("MY_app_port", "2")]))
+        .env_overrides("MY_")
+        .classpath_dir("tests/data/app")
+        .load_default("app", None)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["app"]["port"].as_i64(), Some(2));
+
+    let doc = hocon::HoconLoader::new()
+        .with_env(env(&[("APP_CONFIG_FILE", "tests/data/missing.conf")]))
+        .load_default("app", None);
+    assert!(matches!(dbg!(doc), Err(hocon::Error::File { .. })));
+
+    let doc = hocon::HoconLoader::new()
+        .with_env(env(&[("APP_CONFIG_FILE", "tests/data/missing.conf")]))
+        .load_default("app", Some(std::path::Path::new("tests/data/basic.conf")))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["b"].as_f64(), Some(6.7));
+}
+
+#[test]
+fn profile_overlays() {
+    let load = |loader: hocon::HoconLoader, file: &str| {
+        loader
+            .strict()
+            .load_file(file)
+            .expect("during test")
+            .hocon()
+            .expect("during test")
+    };
+
+    let doc = load(
+        hocon::HoconLoader::new(),
+        "tests/data/profiles/application.conf",
+    );
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("localhost"))
+    );
+
+    let doc = load(
+        hocon::HoconLoader::new().with_profile("prod"),
+        "tests/data/profiles/application.conf",
+    );
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("db.prod.example.com"))
+    );
+    assert_eq!(doc["db"]["pool"].as_i64(), Some(20));
+
+    let doc = load(
+        hocon::HoconLoader::new().with_profile("prod"),
+        "tests/data/profiles/application",
+    );
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("db.prod.example.com"))
+    );
+    assert_eq!(doc["db"]["pool"].as_i64(), Some(20));
+
+    let doc = load(
+        hocon::HoconLoader::new()
+            .with_profile("prod")
+            .with_profile("staging"),
+        "tests/data/profiles/application",
+    );
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("db.staging.example.com"))
+    );
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .load_dir("tests/data/profiles")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("localhost"))
+    );
+
+    let doc = hocon::HoconLoader::new()
+        .strict()
+        .with_profile("staging")
+        .load_dir("tests/data/profiles")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        dbg!(&doc)["db"]["host"].as_string(),
+        Some(String::from("db.staging.example.com"))
+    );
+}
+
+#[test]
+fn dependencies_of_load() {
+    let cwd = std::env::current_dir().expect("during test");
+    let file = |path: &str, found: bool| hocon::Dependency::File {
+        path: cwd.join(path),
+        found,
+    };
+
+    let loader = hocon::HoconLoader::new()
+        .include_path("tests/data/conf.d")
+        .load_file("tests/data/file_with_different_types")
+        .expect("during test")
+        .load_str(
+            r#"{
+                include "missing.conf"
+                include "conf.d/*.json"
+                include classpath("missing")
+            }"#,
+        )
+        .expect("during test")
+        .load_reader(
+            r#"include "10-port.json""#.as_bytes(),
+            hocon::Format::Hocon,
+            Some(std::path::Path::new("tests/data/virtual.conf")),
+        )
+        .expect("during test");
+
+    assert_eq!(
+        dbg!(loader.dependencies()),
+        &[
+            file("tests/data/file_with_different_types.conf", true),
+            file("tests/data/file_with_different_types.json", true),
+            file("tests/data/file_with_different_types.properties", true),
+            file("tests/data/conf.d/missing.conf", false),
+            file("tests/data/conf.d/conf.d", false),
+            file("tests/data/10-port.json", false),
+            file("tests/data/conf.d/10-port.json", true),
+        ][..]
+    );
+
+    let loader = hocon::HoconLoader::new()
+        .with_profile("qa")
+        .load_dir("tests/data/conf.d")
+        .expect("during test");
+    assert_eq!(
+        dbg!(loader.dependencies())[..3],
+        [
+            file("tests/data/conf.d", true),
+            file("tests/data/conf.d/00-base.conf", true),
+            file("tests/data/conf.d/00-base.qa.properties", false),
+        ][..]
+    );
+
+    let loader = hocon::HoconLoader::new()
+        .load_dir("tests/data/missing_dir")
+        .map(|_| ());
+    assert!(loader.is_err());
+    let loader = hocon::HoconLoader::new()
+        .collect_errors()
+        .load_dir("tests/data/missing_dir")
+        .expect("during test");
+    assert_eq!(
+        loader.dependencies(),
+        &[file("tests/data/missing_dir", false)][..]
+    );
+
+    let loader = hocon::HoconLoader::new()
+        .collect_errors()
+        .load_reader(
+            "{\ninclude \"basic.conf\"\ninclude \"missing.conf\"\na: }\n}".as_bytes(),
+            hocon::Format::Hocon,
+            Some(std::path::Path::new("tests/data/virtual.conf")),
+        )
+        .expect("during test");
+    assert_eq!(
+        dbg!(loader.dependencies()),
+        &[
+            file("tests/data/basic.conf", true),
+            file("tests/data/missing.conf", false),
+        ][..]
+    );
+}
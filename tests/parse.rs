@@ -301,6 +301,98 @@ fn parse_error() {
     assert!(doc.is_err());
 }
 
+#[test]
+fn parse_error_position() {
+    let s = "{\n  a: 1\n  b 2\n}";
+    let doc = dbg!(HoconLoader::new().load_str(dbg!(s)));
+
+    assert_eq!(
+        doc.err(),
+        Some(Error::Parse {
+            path: None,
+            line: 3,
+            column: 6,
+            message: String::from("expected ':' or '=' after key"),
+        })
+    );
+
+    let s = "{\n  a { b: 1\n    c: [1, 2\n  }\n}";
+    let doc = dbg!(HoconLoader::new().load_str(dbg!(s)));
+
+    assert_eq!(
+        doc.err(),
+        Some(Error::Parse {
+            path: None,
+            line: 4,
+            column: 3,
+            message: String::from("unexpected '}', expected ']'"),
+        })
+    );
+}
+
+#[test]
+fn parse_error_after_include() {
+    let after_include = String::from(
+        "expected \"file\", file(\"file\"), url(\"url\"), classpath(\"resource\") or required(...) after include",
+    );
+    for s in &[
+        "{\n  a: 1\n  include \"x\n}",
+        "{\n  a: 1\n  include\t\"x\n}",
+        "{\n  a: 1\n  include\n\"x\n}",
+        "{\n  a: 1\n  include\"x\n}",
+        "{\n  a: 1\n  includefile(x)\n}",
+        "{\n  a: 1\n  includerequired(\"x\"\n}",
+    ] {
+        let doc = dbg!(HoconLoader::new().load_str(dbg!(s)));
+        assert_eq!(
+            doc.err(),
+            Some(Error::Parse {
+                path: None,
+                line: 3,
+                column: 3,
+                message: after_include.clone(),
+            })
+        );
+    }
+
+    let doc = dbg!(HoconLoader::new().load_str("{\n  a: 1\n  includes 2\n}"));
+    assert_eq!(
+        doc.err(),
+        Some(Error::Parse {
+            path: None,
+            line: 3,
+            column: 13,
+            message: String::from("expected ':' or '=' after key"),
+        })
+    );
+}
+
+#[test]
+fn parse_error_position_strict_remaining() {
+    let s = "a: 1\r\nb: \"unclosed\r\n";
+    let doc = dbg!(HoconLoader::new().strict().load_str(dbg!(s)));
+
+    assert_eq!(
+        doc.err(),
+        Some(Error::Parse {
+            path: None,
+            line: 2,
+            column: 4,
+            message: String::from("expected closing '\"' for string"),
+        })
+    );
+    assert_eq!(
+        Error::Parse {
+            path: Some(String::from("application.conf")),
+            line: 2,
+            column: 4,
+            message: String::from("expected closing '\"' for string"),
+        }
+        .to_string(),
+        "Error while parsing document 'application.conf' at line 2, column 4: expected closing '\"' for string"
+    );
+}
+
 #[test]
 fn wrong_index() {
     let s = r#"{ "a" : 42 }"#;
@@ -0,0 +1,90 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use hocon::{ConfigWatcher, Hocon, HoconLoader};
+
+#[test]
+fn reload_on_change() {
+    let dir = std::env::temp_dir().join(format!("hocon-watcher-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("during test");
+    let main = dir.join("main.conf");
+    std::fs::write(&main, "include \"extra.conf\"\na = 1\n").expect("during test");
+
+    let (sender, receiver) = mpsc::channel();
+    let to_load = main.clone();
+    let (doc, watcher) = ConfigWatcher::watch(
+        move || HoconLoader::new().strict().load_file(&to_load),
+        Duration::from_millis(10),
+        move |reloaded| sender.send(reloaded).expect("during test"),
+    )
+    .expect("during test");
+    assert_eq!(dbg!(doc)["a"].as_i64(), Some(1));
+
+    let next = || {
+        receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("during test")
+    };
+
+    std::fs::write(&main, "include \"extra.conf\"\na = 22\n").expect("during test");
+    let doc = dbg!(next()).expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(22));
+
+    std::fs::write(dir.join("extra.conf"), "b = 3\n").expect("during test");
+    let doc = dbg!(next()).expect("during test");
+    assert_eq!(doc["b"].as_i64(), Some(3));
+    assert_eq!(doc["a"].as_i64(), Some(22));
+
+    std::fs::write(&main, "a = {\n").expect("during test");
+    assert!(dbg!(next()).is_err());
+
+    std::fs::write(&main, "a = 4444\n").expect("during test");
+    let doc = dbg!(next()).expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(4444));
+    assert_eq!(doc["b"], Hocon::BadValue(hocon::Error::MissingKey));
+
+    drop(watcher);
+    std::fs::remove_dir_all(&dir).expect("during test");
+}
+
+#[test]
+fn reload_on_new_fragment_and_overlay() {
+    let dir = std::env::temp_dir().join(format!("hocon-watcher-dir-{}", std::process::id()));
+    let fragments = dir.join("conf.d");
+    std::fs::create_dir_all(&fragments).expect("during test");
+    std::fs::write(fragments.join("00-base.conf"), "a = 1\n").expect("during test");
+    let application = dir.join("application.conf");
+    std::fs::write(&application, "b = 1\n").expect("during test");
+
+    let (sender, receiver) = mpsc::channel();
+    let (to_load, application_to_load) = (fragments.clone(), application.clone());
+    let (doc, watcher) = ConfigWatcher::watch(
+        move || {
+            HoconLoader::new()
+                .with_profile("prod")
+                .load_dir(&to_load)?
+                .load_file(&application_to_load)
+        },
+        Duration::from_millis(10),
+        move |reloaded| sender.send(reloaded).expect("during test"),
+    )
+    .expect("during test");
+    assert_eq!(dbg!(doc)["a"].as_i64(), Some(1));
+
+    let next = || {
+        receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("during test")
+    };
+
+    std::fs::write(fragments.join("10-override.conf"), "a = 2\n").expect("during test");
+    let doc = dbg!(next()).expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(2));
+
+    std::fs::write(dir.join("application.prod.conf"), "b = 2\n").expect("during test");
+    let doc = dbg!(next()).expect("during test");
+    assert_eq!(doc["b"].as_i64(), Some(2));
+
+    drop(watcher);
+    std::fs::remove_dir_all(&dir).expect("during test");
+}